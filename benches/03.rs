@@ -0,0 +1,7 @@
+#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/03.rs"]
+mod day;
+
+fn main() {
+    advent_of_code::bench::bench_solution(&advent_of_code::solution!(3, day));
+}
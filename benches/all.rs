@@ -0,0 +1,32 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::environment::Environment;
+use advent_of_code::render;
+use advent_of_code::runner::{self, Options};
+use advent_of_code::tuning::{self, Tuning};
+
+#[path = "../src/solutions.rs"]
+mod solutions;
+
+/// Benchmarks every day like `cargo all --release -- --bench --sequential`,
+/// printing each day as it finishes. Filters, baselines and the history are
+/// left to the runner.
+fn main() {
+    let tuning = Tuning {
+        governor: tuning::governor(0),
+        ..Tuning::default()
+    };
+    println!("{}", render::italic(Environment::collect()));
+    println!("{}", render::italic(&tuning));
+    if let Some(warning) = tuning.governor_warning() {
+        eprintln!("{}", warning);
+    }
+
+    let options = Options {
+        bench: true,
+        ..Options::default()
+    };
+    runner::run_days(solutions::SOLUTIONS, 1, options, runner::print_day);
+}
@@ -0,0 +1,105 @@
+/*
+ * Benchmarks for the data structures in `src/helpers/`, run with `cargo bench --bench helpers`.
+ * Where a standard library collection would do the same job, it is timed alongside.
+ */
+use std::collections::HashSet;
+
+use advent_of_code::bench::measure;
+use advent_of_code::helpers::Rng;
+use advent_of_code::helpers::{BitSet, BitSet128, LetterSet, UnionFind};
+use advent_of_code::render;
+
+fn report<T>(name: &str, func: impl Fn(&str) -> T, input: &str) {
+    println!(
+        "{} {}",
+        render::bold(format!("{}:", name)),
+        measure(func, input)
+    );
+}
+
+fn main() {
+    let mut rng = Rng::new(48);
+    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let rucksacks = (0..1000)
+        .map(|_| (0..32).map(|_| *rng.pick(&letters)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    report(
+        "shared letters, LetterSet",
+        |input| {
+            input
+                .lines()
+                .map(|line| {
+                    let (a, b) = line.split_at(line.len() / 2);
+                    (LetterSet::from(a) & LetterSet::from(b)).len()
+                })
+                .sum::<usize>()
+        },
+        &rucksacks,
+    );
+    report(
+        "shared letters, HashSet<char>",
+        |input| {
+            input
+                .lines()
+                .map(|line| {
+                    let (a, b) = line.split_at(line.len() / 2);
+                    let a = a.chars().collect::<HashSet<_>>();
+                    b.chars().collect::<HashSet<_>>().intersection(&a).count()
+                })
+                .sum::<usize>()
+        },
+        &rucksacks,
+    );
+
+    let indices = (0..10_000).map(|_| rng.range(0..128)).collect::<Vec<_>>();
+    report(
+        "insert and union, BitSet128",
+        |_| {
+            indices
+                .chunks(16)
+                .map(|chunk| chunk.iter().copied().collect::<BitSet128>())
+                .fold(BitSet128::EMPTY, |a, b| a | b)
+                .len()
+        },
+        "",
+    );
+    report(
+        "insert and union, BitSet",
+        |_| {
+            indices
+                .chunks(16)
+                .map(|chunk| chunk.iter().copied().collect::<BitSet>())
+                .fold(BitSet::new(), |a, b| a.union(&b))
+                .len()
+        },
+        "",
+    );
+    report(
+        "insert and union, HashSet<usize>",
+        |_| {
+            indices
+                .chunks(16)
+                .map(|chunk| chunk.iter().copied().collect::<HashSet<_>>())
+                .fold(HashSet::new(), |a, b| &a | &b)
+                .len()
+        },
+        "",
+    );
+
+    let edges = (0..100_000)
+        .map(|_| (rng.range(0..100_000), rng.range(0..100_000)))
+        .collect::<Vec<_>>();
+    report(
+        "100k unions, UnionFind",
+        |_| {
+            let mut sets = UnionFind::new(100_000);
+            for &(a, b) in &edges {
+                sets.union(a, b);
+            }
+            sets.sets()
+        },
+        "",
+    );
+}
@@ -0,0 +1,30 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::env;
+use std::process::Command;
+
+/// Passes the build profile, target triple and compiler version on to
+/// `environment.rs`, they are not available at runtime otherwise.
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| String::from("unknown"));
+
+    println!(
+        "cargo:rustc-env=AOC_PROFILE={}",
+        env::var("PROFILE").unwrap_or_else(|_| String::from("unknown"))
+    );
+    println!(
+        "cargo:rustc-env=AOC_TARGET={}",
+        env::var("TARGET").unwrap_or_else(|_| String::from("unknown"))
+    );
+    println!("cargo:rustc-env=AOC_RUSTC={}", version);
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,32 @@
+/*
+ * Derive macros for parsing puzzle input, re-exported by `advent_of_code::helpers`.
+ * The generated code refers to `::advent_of_code`, so they can only be used by the solutions.
+ */
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod pattern;
+mod token;
+
+/// Implements `FromStr` for a struct with named fields from a pattern such as
+/// `#[pattern("move {amount} from {from} to {to}")]`. Every field appears in the
+/// pattern exactly once and is parsed with its own `FromStr`; the error is a
+/// `helpers::parse::ParseError` pointing at the text that failed.
+#[proc_macro_derive(ParsePattern, attributes(pattern))]
+pub fn derive_parse_pattern(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pattern::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromStr`, `TryFrom<char>` and `Display` for an enum whose variants
+/// list the tokens they are parsed from, e.g. `#[token("A", "X")] Rock`. A variant
+/// displays as its first token; errors list every accepted token.
+#[proc_macro_derive(ParseToken, attributes(token))]
+pub fn derive_parse_token(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    token::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Result};
+
+/// A pattern split at its placeholders, there is always one more literal than placeholders.
+#[derive(Debug, PartialEq)]
+struct Pattern {
+    literals: Vec<String>,
+    placeholders: Vec<String>,
+}
+
+/// Splits `pattern` at its `{name}` placeholders, `{{` and `}}` are literal braces.
+fn parse_pattern(pattern: &str) -> std::result::Result<Pattern, String> {
+    let mut literals = vec![String::new()];
+    let mut placeholders = vec![];
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                literals.last_mut().unwrap().push(c);
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder '{{{}'", name)),
+                    }
+                }
+                // without text in between, there is no telling where the first value ends.
+                if !placeholders.is_empty() && literals.last().unwrap().is_empty() {
+                    return Err(format!(
+                        "placeholder '{{{}}}' directly follows another one",
+                        name
+                    ));
+                }
+                placeholders.push(name.trim().to_string());
+                literals.push(String::new());
+            }
+            '}' => return Err(String::from("unmatched '}', use '}}' for a literal brace")),
+            c => literals.last_mut().unwrap().push(c),
+        }
+    }
+    Ok(Pattern {
+        literals,
+        placeholders,
+    })
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            name.span(),
+            "ParsePattern only supports structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            name.span(),
+            "ParsePattern requires a struct with named fields",
+        ));
+    };
+    let attribute = input
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path().is_ident("pattern"))
+        .ok_or_else(|| {
+            Error::new(
+                name.span(),
+                "ParsePattern requires a #[pattern(\"...\")] attribute",
+            )
+        })?;
+    let literal: LitStr = attribute.parse_args()?;
+    let pattern = parse_pattern(&literal.value()).map_err(|e| Error::new(literal.span(), e))?;
+
+    let fields = fields
+        .named
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let field_name = |field: &Ident| field.to_string().trim_start_matches("r#").to_string();
+
+    let mut captured = vec![];
+    for placeholder in &pattern.placeholders {
+        let Some(field) = fields
+            .iter()
+            .find(|field| field_name(field) == *placeholder)
+        else {
+            return Err(Error::new(
+                literal.span(),
+                format!("`{}` has no field named `{}`", name, placeholder),
+            ));
+        };
+        if captured.contains(&field) {
+            return Err(Error::new(
+                literal.span(),
+                format!("field `{}` appears more than once", placeholder),
+            ));
+        }
+        captured.push(field);
+    }
+    if let Some(missing) = fields.iter().find(|field| !captured.contains(field)) {
+        return Err(Error::new(
+            missing.span(),
+            format!("field `{}` does not appear in the pattern", missing),
+        ));
+    }
+
+    let literals = &pattern.literals;
+    let placeholders = &pattern.placeholders;
+    let indices = 0..captured.len();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::advent_of_code::helpers::parse::ParseError;
+
+            fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+                let captures = ::advent_of_code::helpers::parse::match_pattern(line, &[#(#literals),*])?;
+                ::std::result::Result::Ok(Self {
+                    #(#captured: ::advent_of_code::helpers::parse::capture(captures[#indices], #placeholders)?,)*
+                })
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            parse_pattern("move {amount} from {from} to {to}"),
+            Ok(Pattern {
+                literals: vec!["move ", " from ", " to ", ""]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                placeholders: vec!["amount", "from", "to"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            })
+        );
+        assert_eq!(
+            parse_pattern("{{{x}}}").map(|p| p.literals),
+            Ok(vec![String::from("{"), String::from("}")])
+        );
+        assert!(parse_pattern("{a}{b}").is_err());
+        assert!(parse_pattern("{a").is_err());
+        assert!(parse_pattern("a}").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result, Token};
+
+/// Lists tokens the way errors show them: `'A', 'B' or 'C'`.
+fn expected(tokens: &[String]) -> String {
+    let quoted = tokens
+        .iter()
+        .map(|token| format!("'{}'", token))
+        .collect::<Vec<_>>();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.concat(),
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(name.span(), "ParseToken only supports enums"));
+    };
+
+    let mut all_tokens: Vec<String> = vec![];
+    let mut variants = vec![];
+    let mut token_lists = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(
+                variant.ident.span(),
+                "ParseToken only supports variants without fields",
+            ));
+        }
+        let mut tokens = vec![];
+        for attribute in variant.attrs.iter().filter(|a| a.path().is_ident("token")) {
+            let literals =
+                attribute.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+            for literal in literals {
+                let token = literal.value();
+                if token.is_empty() {
+                    return Err(Error::new(literal.span(), "tokens may not be empty"));
+                }
+                if all_tokens.contains(&token) {
+                    return Err(Error::new(
+                        literal.span(),
+                        format!("token '{}' is used more than once", token),
+                    ));
+                }
+                all_tokens.push(token.clone());
+                tokens.push(token);
+            }
+        }
+        if tokens.is_empty() {
+            return Err(Error::new(
+                variant.ident.span(),
+                "ParseToken requires a #[token(\"...\")] attribute on every variant",
+            ));
+        }
+        variants.push(&variant.ident);
+        token_lists.push(tokens);
+    }
+
+    // the first token of a variant is the one it displays as.
+    let canonical = token_lists.iter().map(|tokens| &tokens[0]);
+    let patterns = token_lists.iter().map(|tokens| quote!(#(#tokens)|*));
+    let message = format!("expected {}", expected(&all_tokens));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::advent_of_code::helpers::parse::ParseError;
+
+            fn from_str(text: &str) -> ::std::result::Result<Self, Self::Err> {
+                match text {
+                    #(#patterns => ::std::result::Result::Ok(Self::#variants),)*
+                    _ => ::std::result::Result::Err(
+                        ::advent_of_code::helpers::parse::ParseError::new(1, text, #message),
+                    ),
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::advent_of_code::helpers::parse::ParseError;
+
+            fn try_from(c: char) -> ::std::result::Result<Self, Self::Error> {
+                c.encode_utf8(&mut [0; 4]).parse()
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    #(Self::#variants => #canonical,)*
+                })
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let tokens = |tokens: &[&str]| tokens.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        assert_eq!(expected(&tokens(&["#"])), "'#'");
+        assert_eq!(expected(&tokens(&["#", "."])), "'#' or '.'");
+        assert_eq!(expected(&tokens(&["A", "B", "C"])), "'A', 'B' or 'C'");
+    }
+}
@@ -0,0 +1,182 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// Heap usage of a tracked call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub count: usize,
+    /// Total bytes requested by those allocations.
+    pub bytes: usize,
+    /// Highest number of bytes that were live at the same time.
+    pub peak: usize,
+}
+
+struct Counters {
+    tracking: Cell<bool>,
+    count: Cell<usize>,
+    bytes: Cell<usize>,
+    live: Cell<isize>,
+    peak: Cell<isize>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            tracking: Cell::new(false),
+            count: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    // `try_with` fails while the thread is shutting down, those allocations are not ours.
+    let _ = COUNTERS.try_with(|counters| {
+        if !counters.tracking.get() {
+            return;
+        }
+        if allocated > 0 {
+            counters.count.set(counters.count.get() + 1);
+            counters.bytes.set(counters.bytes.get() + allocated);
+        }
+        let live = counters.live.get() + allocated as isize - freed as isize;
+        counters.live.set(live);
+        counters.peak.set(counters.peak.get().max(live));
+    });
+}
+
+/// A global allocator that counts the allocations of the current thread while `track` runs.
+///
+/// It is installed by enabling the `count-allocations` feature,
+/// e.g. `cargo all --features count-allocations`.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Whether the counting allocator is installed.
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Calls `func` and returns its heap usage on the current thread,
+/// or `None` if the counting allocator is not installed.
+pub fn track<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_enabled() {
+        return (func(), None);
+    }
+
+    COUNTERS.with(|counters| {
+        counters.count.set(0);
+        counters.bytes.set(0);
+        counters.live.set(0);
+        counters.peak.set(0);
+        counters.tracking.set(true);
+    });
+    let result = func();
+    let stats = COUNTERS.with(|counters| {
+        counters.tracking.set(false);
+        AllocStats {
+            count: counters.count.get(),
+            bytes: counters.bytes.get(),
+            peak: counters.peak.get().max(0) as usize,
+        }
+    });
+
+    (result, Some(stats))
+}
+
+/// Formats a byte count with binary units, e.g. `1.50KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1024_f64 {
+            break;
+        }
+        value /= 1024_f64;
+        unit = next;
+    }
+    format!("{:.2}{}", value, unit)
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "allocations: {}, allocated: {}, peak: {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00MiB");
+    }
+
+    #[test]
+    fn test_track() {
+        let (len, stats) = track(|| {
+            let first = vec![0_u8; 100];
+            drop(first);
+            vec![0_u8; 60].len()
+        });
+        assert_eq!(len, 60);
+
+        match stats {
+            Some(stats) => assert_eq!(
+                stats,
+                AllocStats {
+                    count: 2,
+                    bytes: 160,
+                    peak: 100
+                }
+            ),
+            None => assert!(!is_enabled()),
+        }
+    }
+}
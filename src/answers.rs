@@ -0,0 +1,117 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fs;
+use std::io;
+
+use crate::runner::PartResult;
+
+/// Correct answers of a day's parts, stored in `src/answers/NN.txt` with
+/// part one on the first line and part two on the second. An empty line
+/// marks a part whose answer is not known yet.
+pub type Answers = [Option<String>; 2];
+
+pub fn parse(contents: &str) -> Answers {
+    let mut lines = contents.lines().map(|line| {
+        let line = line.trim();
+        (!line.is_empty()).then(|| line.to_string())
+    });
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+pub fn format(answers: &Answers) -> String {
+    answers
+        .iter()
+        .map(|answer| format!("{}\n", answer.as_deref().unwrap_or_default()))
+        .collect()
+}
+
+/// Loads the stored answers of `day`, none if the file is missing.
+pub fn load(day: u8) -> Answers {
+    crate::try_read_file("answers", day)
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+/// Fills the parts of `answers` without a stored answer with the answers in
+/// `results`. Stored answers are only replaced if `overwrite` is set, since a
+/// part that regressed would otherwise store its wrong answer.
+pub fn merge(answers: &mut Answers, results: &[PartResult], overwrite: bool) {
+    for result in results {
+        let stored = &mut answers[result.part as usize - 1];
+        if let Some(answer) = result
+            .answer
+            .as_ref()
+            .filter(|_| overwrite || stored.is_none())
+        {
+            *stored = Some(answer.clone());
+        }
+    }
+}
+
+/// Stores the answers of the solved parts in `results`, see `merge`.
+/// Returns the changed days.
+pub fn save(results: &[PartResult], overwrite: bool) -> io::Result<Vec<u8>> {
+    let mut changed = vec![];
+    let mut days = results.iter().map(|result| result.day).collect::<Vec<_>>();
+    days.dedup();
+
+    for day in days {
+        let mut answers = load(day);
+        let before = answers.clone();
+        let day_results = results
+            .iter()
+            .filter(|result| result.day == day)
+            .cloned()
+            .collect::<Vec<_>>();
+        merge(&mut answers, &day_results, overwrite);
+        if answers != before {
+            fs::write(crate::file_path("answers", day), format(&answers))?;
+            changed.push(day);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_and_format() {
+        assert_eq!(
+            parse("24000\n45000\n"),
+            [Some("24000".into()), Some("45000".into())]
+        );
+        assert_eq!(parse("\nCMZ"), [None, Some("CMZ".into())]);
+        assert_eq!(parse(""), [None, None]);
+
+        let answers = [Some("CMZ".into()), None];
+        assert_eq!(format(&answers), "CMZ\n\n");
+        assert_eq!(parse(&format(&answers)), answers);
+    }
+
+    #[test]
+    fn test_merge() {
+        let result = |part, answer: &str| PartResult {
+            day: 1,
+            part,
+            answer: Some(answer.into()),
+            elapsed: Duration::ZERO,
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: None,
+        };
+        let results = [result(1, "wrong"), result(2, "45000")];
+
+        let mut answers = [Some("24000".into()), None];
+        merge(&mut answers, &results, false);
+        assert_eq!(answers, [Some("24000".into()), Some("45000".into())]);
+
+        merge(&mut answers, &results, true);
+        assert_eq!(answers, [Some("wrong".into()), Some("45000".into())]);
+    }
+}
@@ -0,0 +1,350 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::render;
+use crate::runner::PartResult;
+
+/// How a timing was taken. Only timings of the same kind are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Measurement {
+    /// The elapsed time of a single run.
+    Single,
+    /// The median of repeated runs with `--bench`.
+    Median,
+}
+
+impl Measurement {
+    pub fn of(result: &PartResult) -> Measurement {
+        match result.stats {
+            Some(_) => Measurement::Median,
+            None => Measurement::Single,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Measurement::Single => "single",
+            Measurement::Median => "median",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Measurement> {
+        match name {
+            "single" => Some(Measurement::Single),
+            "median" => Some(Measurement::Median),
+            _ => None,
+        }
+    }
+}
+
+/// Timings of every solved part and how they were taken, keyed by `(day, part)`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    timings: BTreeMap<(u8, u8), (Measurement, Duration)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Unchanged,
+    Regressed,
+    /// The part has a baseline timing but now times out, panics or gives no answer.
+    Failed,
+}
+
+/// A part's timing compared against its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change in percent, positive when slower.
+    pub change: f64,
+    pub verdict: Verdict,
+    /// Why the part failed, only set for `Verdict::Failed`.
+    pub failure: Option<String>,
+}
+
+impl Baseline {
+    pub fn from_results(results: &[PartResult]) -> Baseline {
+        Baseline {
+            timings: results
+                .iter()
+                .filter(|result| result.is_solved())
+                .map(|result| {
+                    (
+                        (result.day, result.part),
+                        (Measurement::of(result), result.time()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds or replaces the timings of the solved parts in `results`, keeping
+    /// those of parts that did not run, e.g. because of a day filter.
+    pub fn merge(&mut self, results: &[PartResult]) {
+        self.timings.extend(Baseline::from_results(results).timings);
+    }
+
+    pub fn parse(contents: &str) -> Result<Baseline, String> {
+        let mut timings = BTreeMap::new();
+        for (idx, line) in contents.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let parsed = match fields[..] {
+                [day, part, measurement, nanos] => day
+                    .parse()
+                    .ok()
+                    .zip(part.parse().ok())
+                    .zip(Measurement::parse(measurement))
+                    .zip(nanos.parse().ok()),
+                _ => None,
+            };
+            match parsed {
+                Some((((day, part), measurement), nanos)) => {
+                    timings.insert((day, part), (measurement, Duration::from_nanos(nanos)));
+                }
+                None => return Err(format!("invalid baseline entry on line {}", idx + 1)),
+            }
+        }
+        Ok(Baseline { timings })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        Baseline::parse(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    /// Compares every solved part that also has a baseline timing of the same
+    /// kind, see `Measurement`. Changes beyond `threshold` percent are marked
+    /// as improved or regressed. Parts with a baseline timing that did not
+    /// produce an answer this time are marked as failed.
+    pub fn compare(&self, results: &[PartResult], threshold: f64) -> Vec<Comparison> {
+        results
+            .iter()
+            .filter_map(|result| {
+                let &(measurement, baseline) = self.timings.get(&(result.day, result.part))?;
+                let current = result.time();
+                if !result.is_solved() {
+                    return Some(Comparison {
+                        day: result.day,
+                        part: result.part,
+                        baseline,
+                        current,
+                        change: 0_f64,
+                        verdict: Verdict::Failed,
+                        failure: Some(match &result.error {
+                            Some(error) => error.to_string(),
+                            None => String::from("not solved"),
+                        }),
+                    });
+                }
+                if measurement != Measurement::of(result) {
+                    return None;
+                }
+
+                let change = if baseline.is_zero() {
+                    0_f64
+                } else {
+                    (current.as_secs_f64() / baseline.as_secs_f64() - 1_f64) * 100_f64
+                };
+                let verdict = if change > threshold {
+                    Verdict::Regressed
+                } else if change < -threshold {
+                    Verdict::Improved
+                } else {
+                    Verdict::Unchanged
+                };
+
+                Some(Comparison {
+                    day: result.day,
+                    part: result.part,
+                    baseline,
+                    current,
+                    change,
+                    verdict,
+                    failure: None,
+                })
+            })
+            .collect()
+    }
+
+    /// The number of solved parts whose baseline timing was taken differently,
+    /// e.g. with `--bench` when the current run was a single one.
+    pub fn mismatched(&self, results: &[PartResult]) -> usize {
+        results
+            .iter()
+            .filter(|result| result.is_solved())
+            .filter(|result| {
+                self.timings
+                    .get(&(result.day, result.part))
+                    .is_some_and(|&(measurement, _)| measurement != Measurement::of(result))
+            })
+            .count()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day\tpart\tmeasurement\tnanos")?;
+        for ((day, part), (measurement, time)) in &self.timings {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}",
+                day,
+                part,
+                measurement.name(),
+                time.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(failure) = &self.failure {
+            return write!(
+                f,
+                "Day {:02} Part {}: {} -> {} {}",
+                self.day,
+                self.part,
+                render::duration(self.baseline),
+                failure,
+                render::bold("FAILED")
+            );
+        }
+        let marker = match self.verdict {
+            Verdict::Improved => format!(" {}", render::bold("improved")),
+            Verdict::Unchanged | Verdict::Failed => String::new(),
+            Verdict::Regressed => format!(" {}", render::bold("REGRESSED")),
+        };
+        write!(
+            f,
+            "Day {:02} Part {}: {} -> {} ({:+.1}%){}",
+            self.day,
+            self.part,
+            render::duration(self.baseline),
+            render::duration(self.current),
+            self.change,
+            marker
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Stats;
+    use crate::runner::PartError;
+
+    fn result(day: u8, part: u8, micros: u64) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: Some("0".into()),
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let baseline = Baseline::from_results(&[result(1, 1, 100), result(1, 2, 250)]);
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline));
+        assert!(Baseline::parse("1\t1\tsingle\tfast").is_err());
+        assert!(Baseline::parse("1\t1\t100").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut baseline = Baseline::from_results(&[result(1, 1, 100), result(2, 1, 100)]);
+        baseline.merge(&[result(2, 1, 50), result(3, 1, 10)]);
+        assert_eq!(
+            baseline,
+            Baseline::from_results(&[result(1, 1, 100), result(2, 1, 50), result(3, 1, 10)])
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline::from_results(&[
+            result(1, 1, 100),
+            result(1, 2, 100),
+            result(2, 1, 100),
+            result(2, 2, 20_000),
+        ]);
+        let mut timed_out = result(2, 2, 1_000_000);
+        timed_out.answer = None;
+        timed_out.error = Some(PartError::TimedOut(Duration::from_secs(1)));
+        let comparisons = baseline.compare(
+            &[
+                result(1, 1, 150),
+                result(1, 2, 97),
+                result(2, 1, 50),
+                timed_out,
+                result(3, 1, 10),
+            ],
+            5_f64,
+        );
+
+        let verdicts = comparisons
+            .iter()
+            .map(|c| (c.day, c.part, c.verdict))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verdicts,
+            vec![
+                (1, 1, Verdict::Regressed),
+                (1, 2, Verdict::Unchanged),
+                (2, 1, Verdict::Improved),
+                (2, 2, Verdict::Failed),
+            ]
+        );
+        assert!((comparisons[0].change - 50_f64).abs() < 1.0e-6);
+        assert_eq!(
+            comparisons[3].failure.as_deref(),
+            Some("timed out (limit: 1.00s)")
+        );
+    }
+
+    #[test]
+    fn test_compare_like_with_like() {
+        let mut benched = result(1, 1, 100);
+        benched.stats = Some(Stats::from_samples(&[Duration::from_micros(50)], 1));
+        let baseline = Baseline::from_results(&[benched.clone(), result(1, 2, 100)]);
+        let current = [result(1, 1, 100), result(1, 2, 100)];
+
+        let comparisons = baseline.compare(&current, 5_f64);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 2);
+        assert_eq!(baseline.mismatched(&current), 1);
+        assert_eq!(
+            baseline.compare(&[benched], 5_f64)[0].verdict,
+            Verdict::Unchanged
+        );
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline));
+    }
+}
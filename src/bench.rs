@@ -0,0 +1,170 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::{render, Solution};
+
+const WARM_UP_TIME: Duration = Duration::from_millis(200);
+const MEASUREMENT_TIME: Duration = Duration::from_secs(2);
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(1);
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100;
+
+/// Summary of repeated timings of a single solution part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub iterations: u32,
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Builds the summary from per-iteration sample times. `samples` must not be empty.
+    pub fn from_samples(samples: &[Duration], iterations: u32) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let nanos = sorted.iter().map(|sample| sample.as_nanos() as f64);
+        let mean = nanos.clone().sum::<f64>() / n as f64;
+        let variance = nanos.map(|sample| (sample - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Stats {
+            samples: n,
+            iterations,
+            mean: Duration::from_nanos(mean.round() as u64),
+            median,
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            min: sorted[0],
+            max: sorted[n - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = render::mode();
+        write!(
+            f,
+            "{} {} {} {}",
+            mode.duration(self.mean),
+            mode.symbol("±", "+/-"),
+            mode.duration(self.std_dev),
+            mode.italic(format!(
+                "(median: {}, min: {}, max: {}, {} samples {} {} iterations)",
+                mode.duration(self.median),
+                mode.duration(self.min),
+                mode.duration(self.max),
+                self.samples,
+                mode.symbol("×", "x"),
+                self.iterations
+            ))
+        )
+    }
+}
+
+/// Repeatedly runs `func` against `input` and summarizes the timings.
+///
+/// After a short warm-up, calls are batched so that every sample takes at least
+/// a millisecond, which keeps timer overhead out of the numbers for fast parts.
+pub fn measure<T>(func: impl Fn(&str) -> T, input: &str) -> Stats {
+    let mut warm_up_runs = 0_u32;
+    let warm_up = Instant::now();
+    while warm_up_runs == 0 || warm_up.elapsed() < WARM_UP_TIME {
+        black_box(func(black_box(input)));
+        warm_up_runs += 1;
+    }
+    let estimate = (warm_up.elapsed() / warm_up_runs).max(Duration::from_nanos(1));
+
+    let iterations =
+        (MIN_SAMPLE_TIME.as_nanos() / estimate.as_nanos()).clamp(1, u32::MAX as u128) as u32;
+    let sample_time = estimate.saturating_mul(iterations);
+    let samples = ((MEASUREMENT_TIME.as_nanos() / sample_time.as_nanos()) as usize)
+        .clamp(MIN_SAMPLES, MAX_SAMPLES);
+
+    let timings = (0..samples)
+        .map(|_| {
+            let timer = Instant::now();
+            for _ in 0..iterations {
+                black_box(func(black_box(input)));
+            }
+            timer.elapsed() / iterations
+        })
+        .collect::<Vec<_>>();
+
+    Stats::from_samples(&timings, iterations)
+}
+
+/// An upper bound for how long `measure` takes when a single call of the
+/// measured function takes at most `run_limit`, with room for timer noise.
+pub fn time_limit(run_limit: Duration) -> Duration {
+    (WARM_UP_TIME + MEASUREMENT_TIME) * 2 + run_limit * (MIN_SAMPLES as u32 + 1)
+}
+
+/// Benchmarks both parts of `solution` against its puzzle input.
+/// Days without an input file are skipped.
+pub fn bench_solution(solution: &Solution) {
+    render::print_day_header(solution.day);
+
+    let input = match crate::try_read_file("inputs", solution.day) {
+        Some(input) => input,
+        None => {
+            println!("No input, skipped.");
+            return;
+        }
+    };
+
+    for (part, func) in solution.parts.iter().enumerate() {
+        match func(&input) {
+            Some(_) => {
+                let stats = measure(func, &input);
+                println!("{} {}", render::bold(format!("Part {}:", part + 1)), stats);
+            }
+            None => println!(
+                "{} not solved.",
+                render::bold(format!("Part {}:", part + 1))
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&samples, 10);
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.iterations, 10);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.max, Duration::from_micros(4));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1118));
+    }
+
+    #[test]
+    fn test_measure() {
+        let stats = measure(|input| input.len(), "abc");
+
+        assert!(stats.samples >= MIN_SAMPLES && stats.samples <= MAX_SAMPLES);
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+}
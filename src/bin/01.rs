@@ -1,29 +1,16 @@
-use std::num::ParseIntError;
+use advent_of_code::helpers::parse::{blocks, lines, value};
 
-use std::str::FromStr;
 struct Elf {
     calories: u32,
 }
 
-impl FromStr for Elf {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let calories = s
-            .lines()
-            .map(|line| line.parse::<u32>())
-            .sum::<Result<u32, ParseIntError>>()?;
-        Ok(Elf { calories })
-    }
-}
-
 fn parse_to_elfs(input: &str) -> Vec<Elf> {
-    let elfs = input
-        .split("\n\n")
-        .map(|elf| elf.to_owned().parse::<Elf>().unwrap())
-        .collect();
+    let elfs = blocks(input, |elf| {
+        let calories = lines(elf, value::<u32>)?.into_iter().sum();
+        Ok(Elf { calories })
+    });
 
-    elfs
+    elfs.unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
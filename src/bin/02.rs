@@ -1,12 +1,22 @@
+use advent_of_code::helpers::parse::{lines, split, ParseToken};
+
+#[derive(Clone, Copy, ParseToken)]
 enum Shape {
+    #[token("A", "X")]
     Rock,
+    #[token("B", "Y")]
     Paper,
+    #[token("C", "Z")]
     Scissors,
 }
 
+#[derive(ParseToken)]
 enum MatchResult {
+    #[token("X")]
     Loss,
+    #[token("Y")]
     Draw,
+    #[token("Z")]
     Win,
 }
 
@@ -45,68 +55,32 @@ fn calc_score(player_one: Shape, player_two: Shape) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let score = input
-        .lines()
-        .map(|line| {
-            let mut round = line.split_whitespace();
-
-            let player_one = round.next().unwrap();
-            let player_two = round.next().unwrap();
-
-            let player_one = match player_one {
-                "A" => Shape::Rock,
-                "B" => Shape::Paper,
-                "C" => Shape::Scissors,
-                _ => panic!("Invalid input: {}", player_one),
-            };
-
-            let player_two = match player_two {
-                "X" => Shape::Rock,
-                "Y" => Shape::Paper,
-                "Z" => Shape::Scissors,
-                _ => panic!("Invalid input {}", player_two),
-            };
-
-            calc_score(player_one, player_two)
-        })
+    let rounds = lines(input, |line| split::<(Shape, Shape)>(line, &[' '])).unwrap();
+    let score = rounds
+        .into_iter()
+        .map(|(player_one, player_two)| calc_score(player_one, player_two))
         .sum::<u32>();
 
     Some(score)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let score = input
-        .lines()
-        .map(|line| {
-            let mut round = line.split_whitespace();
-
-            let player_one = round.next().unwrap();
-            let player_two = round.next().unwrap();
-
-            let player_one = match player_one {
-                "A" => Shape::Rock,
-                "B" => Shape::Paper,
-                "C" => Shape::Scissors,
-                _ => panic!("Invalid input: {}", player_one),
-            };
-
-            let player_two = match player_two {
-                "X" => match player_one {
+    let rounds = lines(input, |line| split::<(Shape, MatchResult)>(line, &[' '])).unwrap();
+    let score = rounds
+        .into_iter()
+        .map(|(player_one, result)| {
+            let player_two = match result {
+                MatchResult::Loss => match player_one {
                     Shape::Rock => Shape::Scissors,
                     Shape::Paper => Shape::Rock,
                     Shape::Scissors => Shape::Paper,
                 },
-                "Y" => match player_one {
-                    Shape::Rock => Shape::Rock,
-                    Shape::Paper => Shape::Paper,
-                    Shape::Scissors => Shape::Scissors,
-                },
-                "Z" => match player_one {
+                MatchResult::Draw => player_one,
+                MatchResult::Win => match player_one {
                     Shape::Rock => Shape::Paper,
                     Shape::Paper => Shape::Scissors,
                     Shape::Scissors => Shape::Rock,
                 },
-                _ => panic!("Invalid input {}", player_two),
             };
             calc_score(player_one, player_two)
         })
@@ -1,27 +1,11 @@
+use advent_of_code::helpers::LetterSet;
+
 pub fn part_one(input: &str) -> Option<u32> {
     let priorities = input.lines().map(|rucksack| {
         let (first_half, second_half) = rucksack.split_at(rucksack.len() / 2);
 
-        let shared_char = first_half
-            .chars()
-            .find_map(|c| {
-                if second_half.contains(c) {
-                    Some(c)
-                } else {
-                    None
-                }
-            });
-
-        match shared_char {
-            Some(c) => {
-                if c.is_lowercase() {
-                    Some((c as u32) - ('a' as u32) + 1)
-                } else {
-                    Some((c as u32) - ('A' as u32) + 27)
-                }
-            }
-            None => None,
-        }
+        let shared = LetterSet::from(first_half) & LetterSet::from(second_half);
+        shared.priorities().next().map(|priority| priority as u32)
     });
 
     priorities.sum()
@@ -30,38 +14,42 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let lines = input.lines().collect::<Vec<&str>>();
 
-    let mut sum = 0;
-    for idx in 0..(lines.len() / 3) {
-        let (first, second, third) = (
-            lines.get(idx * 3),
-            lines.get(idx * 3 + 1),
-            lines.get(idx * 3 + 2),
-        );
-        if first.is_none() || second.is_none() || third.is_none() {
-            return None;
-        }
+    let badges = lines.chunks_exact(3).map(|group| {
+        let shared = group
+            .iter()
+            .map(|&rucksack| LetterSet::from(rucksack))
+            .reduce(|a, b| a & b)?;
+        shared.priorities().next().map(|priority| priority as u32)
+    });
 
-        let (first, second, third) = (first.unwrap(), second.unwrap(), third.unwrap());
+    badges.sum()
+}
 
-        let shared_char = first
-            .chars()
-            .find_map(|c| {
-                if second.contains(c) && third.contains(c) {
-                    Some(c)
-                } else {
-                    None
-                }
-            })
-            .unwrap();
+/// `size` rucksacks in groups of three, every rucksack holds its group's
+/// badge in both compartments.
+pub fn generate(size: usize) -> String {
+    let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let mut rng = advent_of_code::helpers::Rng::new(3);
+    let mut input = String::new();
 
-        if shared_char.is_lowercase() {
-            sum += (shared_char as u32) - ('a' as u32) + 1;
-        } else {
-            sum += (shared_char as u32) - ('A' as u32) + 27;
+    for _ in 0..size.div_ceil(3) {
+        let badge = *rng.pick(&items);
+        for _ in 0..3 {
+            let half = rng.range(8..24);
+            for _ in 0..2 {
+                let position = rng.range(0..half);
+                for idx in 0..half {
+                    input.push(if idx == position {
+                        badge
+                    } else {
+                        *rng.pick(&items)
+                    });
+                }
+            }
+            input.push('\n');
         }
     }
-
-    Some(sum)
+    input
 }
 
 fn main() {
@@ -85,4 +73,12 @@ mod tests {
         let input = advent_of_code::read_file("examples", 3);
         assert_eq!(part_two(&input), Some(70));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100);
+        assert_eq!(input.lines().count(), 102);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
 }
@@ -1,55 +1,27 @@
-use std::str::FromStr;
+use advent_of_code::helpers::parse::{lines, split};
+use advent_of_code::helpers::Interval;
 
-struct Section {
-    start: u32,
-    end: u32,
-}
 struct ElfPair {
-    first: Section,
-    second: Section,
-}
-
-impl FromStr for Section {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split('-');
-        let start = parts.next().unwrap().parse().unwrap();
-        let end = parts.next().unwrap().parse().unwrap();
-        Ok(Section { start, end })
-    }
+    first: Interval,
+    second: Interval,
 }
 
-impl FromStr for ElfPair {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sections = s.split(",").map(|s| s.parse::<Section>().unwrap());
-
-        let first = sections.next().unwrap();
-        let second = sections.next().unwrap();
-
+fn parse_sections(input: &str) -> Vec<ElfPair> {
+    let sections = lines(input, |line| {
+        let (a, b, c, d) = split(line, &['-', ','])?;
         Ok(ElfPair {
-            first,
-            second,
+            first: Interval::inclusive(a, b),
+            second: Interval::inclusive(c, d),
         })
-    }
-}
-
-fn parse_sections(input: &str) -> Vec<ElfPair> {
-    let sections = input
-        .lines()
-        .map(|s| s.parse::<ElfPair>().unwrap())
-        .collect::<Vec<ElfPair>>();
-    sections
+    });
+    sections.unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let elf_pairs = parse_sections(input);
 
     let full_overlaps = elf_pairs.iter().filter(|pair| {
-        (pair.first.start <= pair.second.start && pair.first.end >= pair.second.end)
-            || (pair.second.start <= pair.first.start && pair.first.end <= pair.second.end)
+        pair.first.contains_interval(&pair.second) || pair.second.contains_interval(&pair.first)
     });
 
     Some(full_overlaps.count().try_into().unwrap())
@@ -58,10 +30,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let elf_pairs = parse_sections(input);
 
-    let overlaps = elf_pairs.iter().filter(|pair| {
-        (pair.first.start >= pair.second.start && pair.first.start <= pair.second.end)
-            || (pair.second.start >= pair.first.start && pair.second.start <= pair.first.end)
-    });
+    let overlaps = elf_pairs
+        .iter()
+        .filter(|pair| pair.first.overlaps(&pair.second));
 
     Some(overlaps.count().try_into().unwrap())
 }
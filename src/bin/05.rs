@@ -1,25 +1,11 @@
-use regex::Regex;
-use std::str::FromStr;
-struct Move {
-    amount: u32,
-    from: u32,
-    to: u32,
-}
-
-impl FromStr for Move {
-    type Err = ();
+use advent_of_code::helpers::parse::{self, ParsePattern};
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-        let digits = regex
-            .replace(s, "$1 $2 $3")
-            .split_whitespace()
-            .map(|s| s.parse::<u32>().unwrap())
-            .collect::<Vec<u32>>();
-
-        let (amount, from, to) = (digits[0], digits[1] - 1, digits[2] - 1);
-        Ok(Move { amount, from, to })
-    }
+#[derive(ParsePattern)]
+#[pattern("move {amount} from {from} to {to}")]
+struct Move {
+    amount: usize,
+    from: usize,
+    to: usize,
 }
 
 fn parse_input_parts(input: &str) -> (Vec<Vec<char>>, Vec<Move>) {
@@ -40,15 +26,12 @@ fn parse_input_parts(input: &str) -> (Vec<Vec<char>>, Vec<Move>) {
         })
     });
 
-    let moves = moves_input
-        .lines()
-        .map(|s| s.parse::<Move>().unwrap())
-        .collect::<Vec<Move>>();
+    let moves = parse::lines(moves_input, str::parse::<Move>).unwrap();
 
     (stacks, moves)
 }
 
-fn get_top_crates(stacks: &Vec<Vec<char>>) -> String {
+fn get_top_crates(stacks: &[Vec<char>]) -> String {
     stacks
         .iter()
         .map(|s| {
@@ -67,16 +50,16 @@ pub fn part_one(input: &str) -> Option<String> {
     let (mut stacks, moves) = parse_input_parts(input);
 
     moves.iter().for_each(|m| {
-        let mut from_stack = stacks[m.from as usize].clone();
-        let mut to_stack = stacks[m.to as usize].clone();
+        let mut from_stack = stacks[m.from - 1].clone();
+        let mut to_stack = stacks[m.to - 1].clone();
         let mut amount = m.amount;
         while amount > 0 {
             let top = from_stack.pop().unwrap();
             to_stack.push(top);
             amount -= 1;
         }
-        stacks[m.from as usize] = from_stack;
-        stacks[m.to as usize] = to_stack;
+        stacks[m.from - 1] = from_stack;
+        stacks[m.to - 1] = to_stack;
     });
 
     let top_crates = get_top_crates(&stacks);
@@ -87,14 +70,14 @@ pub fn part_two(input: &str) -> Option<String> {
     let (mut stacks, moves) = parse_input_parts(input);
 
     moves.iter().for_each(|m| {
-        let mut from_stack = stacks[m.from as usize].clone();
-        let mut to_stack = stacks[m.to as usize].clone();
+        let mut from_stack = stacks[m.from - 1].clone();
+        let mut to_stack = stacks[m.to - 1].clone();
         let popped = from_stack
-            .drain((from_stack.len() - m.amount as usize)..)
+            .drain((from_stack.len() - m.amount)..)
             .collect::<Vec<char>>();
         to_stack.extend(popped);
-        stacks[m.from as usize] = from_stack;
-        stacks[m.to as usize] = to_stack;
+        stacks[m.from - 1] = from_stack;
+        stacks[m.to - 1] = to_stack;
     });
 
     let top_crates = get_top_crates(&stacks);
@@ -1,19 +1,10 @@
+use advent_of_code::helpers::LetterSet;
+
 fn get_signal_marker(signal: &str, length: usize) -> Option<usize> {
-    let mut index: usize = 0;
-    'outer: while let Some(word) = signal.get(index..index + length) {
-        for (i, c) in word.chars().enumerate() {
-            if let Some(rest) = word.get(i + 1..) {
-                if let Some(_idx_of_dup) = rest.find(c) {
-                    // The rest of the string also contains the current
-                    // character so we can increment the base index to the
-                    // current index plus 1 and continue the outer loop.
-                    index += i + 1;
-                    continue 'outer;
-                }
-            }
-        }
-        break;
-    }
+    let chars = signal.chars().collect::<Vec<char>>();
+    let index = chars
+        .windows(length)
+        .position(|window| LetterSet::from_iter(window.iter().copied()).len() == length)?;
 
     Some(index + length)
 }
@@ -32,6 +23,21 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(marker_idx as u32)
 }
 
+/// A signal of about `size` characters that only has its markers at the very end.
+/// Every other character is an `a`, so no four in a row are distinct, while
+/// the ones in between cycle through 13 letters to keep the windows of
+/// part two close to a marker.
+pub fn generate(size: usize) -> String {
+    let mut signal = "bcdefghijklmn"
+        .chars()
+        .cycle()
+        .take(size / 2)
+        .flat_map(|c| ['a', c])
+        .collect::<String>();
+    signal.push_str("opqrstuvwxyzbc");
+    signal
+}
+
 fn main() {
     let input = &advent_of_code::read_file("inputs", 6);
     advent_of_code::solve!(1, part_one, input);
@@ -44,31 +50,26 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one("mjqjpqmgbljsphdztnvjfqwrcgsmlb".into()), Some(7));
-        assert_eq!(part_one("bvwbjplbgvbhsrlpgdmjqwftvncz".into()), Some(5));
-        assert_eq!(part_one("nppdvjthqldpwncqszvftbrmjlhg".into()), Some(6));
-        assert_eq!(
-            part_one("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".into()),
-            Some(10)
-        );
-        assert_eq!(
-            part_one("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".into()),
-            Some(11)
-        );
+        assert_eq!(part_one("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(7));
+        assert_eq!(part_one("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
+        assert_eq!(part_one("nppdvjthqldpwncqszvftbrmjlhg"), Some(6));
+        assert_eq!(part_one("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(10));
+        assert_eq!(part_one("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two("mjqjpqmgbljsphdztnvjfqwrcgsmlb".into()), Some(19));
-        assert_eq!(part_two("bvwbjplbgvbhsrlpgdmjqwftvncz".into()), Some(23));
-        assert_eq!(part_two("nppdvjthqldpwncqszvftbrmjlhg".into()), Some(23));
-        assert_eq!(
-            part_two("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".into()),
-            Some(29)
-        );
-        assert_eq!(
-            part_two("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".into()),
-            Some(26)
-        );
+        assert_eq!(part_two("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(19));
+        assert_eq!(part_two("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
+        assert_eq!(part_two("nppdvjthqldpwncqszvftbrmjlhg"), Some(23));
+        assert_eq!(part_two("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
+        assert_eq!(part_two("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
+    }
+
+    #[test]
+    fn test_generate() {
+        // both markers start at the last "f" of the alternating part.
+        assert_eq!(part_one(&generate(1000)), Some(1001));
+        assert_eq!(part_two(&generate(1000)), Some(1011));
     }
 }
@@ -117,7 +117,7 @@ fn parse_input(input: &str) -> Result<HashMap<String, usize>, Day7ParseError> {
     Ok(folders)
 }
 
-pub fn part_one<'a>(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u32> {
     let folders = parse_input(input).ok()?;
     let over_100k: usize = folders.values().filter(|&&size| size <= 100_000).sum();
 
@@ -1,90 +1,51 @@
-fn get_visible_trees(trees: Vec<Vec<u8>>) -> Vec<Vec<bool>> {
-    let max_length = trees.len() - 1;
-    let mut visible_trees: Vec<Vec<bool>> = trees
-        .iter()
-        .enumerate()
-        .map(|(row_idx, row)| {
-            let line_max_length = row.len() - 1;
-            row.iter()
-                .enumerate()
-                .map(|(col_idx, tree)| {
-                    row_idx == 0
-                        || row_idx == max_length
-                        || col_idx == 0
-                        || col_idx == line_max_length
-                })
-            .collect()
-        })
-    .collect();
+use advent_of_code::helpers::{Direction, Grid};
 
-    for row in 0..trees.len() {
-        let mut max_west = 0;
-        let mut max_east = 0;
-        let mut max_north = 0;
-        let mut max_south = 0;
-
-        for col in 0..trees[0].len() {
-            let east_col_idx = trees[0].len() - 1 - col;
-            let south_row_idx = trees.len() - 1 - row;
-            if row == 0 {
-                max_west = trees[row][col];
-                max_east = trees[row][east_col_idx];
-                max_north = trees[col][row];
-                max_south = trees[col][south_row_idx];
-            } else {
-                // WEST
-                if trees[row][col] > max_west {
-                    max_west = trees[row][col];
-                    visible_trees[row][col] = true;
-                }
-
-                // EAST
-                if trees[row][east_col_idx] > max_east {
-                    max_east = trees[row][east_col_idx];
-                    visible_trees[row][east_col_idx] = true;
-                }
-
-                // NORTH
-                if trees[col][row] > max_north {
-                    max_north = trees[col][row];
-                    visible_trees[col][row] = true;
-                }
+fn parse_input(input: &str) -> Grid<u8> {
+    Grid::parse(input, |c| c.to_digit(10).unwrap() as u8).unwrap()
+}
 
-                // SOUTH
-                if trees[col][south_row_idx] > max_south {
-                    max_south = trees[col][south_row_idx];
-                    visible_trees[col][south_row_idx] = true;
-                }
-            }
+/// Marks every tree that is taller than all trees before it, walking from
+/// `start` in the direction of `step` to the opposite edge.
+fn mark_visible(
+    trees: &Grid<u8>,
+    visible: &mut Grid<bool>,
+    start: (usize, usize),
+    step: Direction,
+) {
+    let mut max_height = None;
+    for (position, &height) in trees.line(start, step) {
+        if max_height.is_none_or(|max| height > max) {
+            max_height = Some(height);
+            visible[position] = true;
         }
     }
-
-    visible_trees
 }
 
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    let parsed_trees: Vec<Vec<u8>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect()
-        })
-    .collect();
-
-    parsed_trees
+/// Scans every row and column from both edges, so each tree is looked at four times.
+fn get_visible_trees(trees: &Grid<u8>) -> Grid<bool> {
+    let (width, height) = (trees.width(), trees.height());
+    let mut visible = Grid::new(width, height, false);
+    for row in 0..height {
+        mark_visible(trees, &mut visible, (row, 0), Direction::Right);
+        mark_visible(trees, &mut visible, (row, width - 1), Direction::Left);
+    }
+    for col in 0..width {
+        mark_visible(trees, &mut visible, (0, col), Direction::Down);
+        mark_visible(trees, &mut visible, (height - 1, col), Direction::Up);
+    }
+    visible
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let trees = parse_input(input);
-    let visible_trees = get_visible_trees(trees);
+    let visible_trees = get_visible_trees(&trees);
 
-    let num_visible = visible_trees.iter().flatten().filter(|&&tree| tree).count();
+    let num_visible = visible_trees.iter().filter(|(_, &tree)| tree).count();
 
     Some(num_visible as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
 
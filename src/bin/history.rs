@@ -0,0 +1,57 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::history::{self, History};
+use advent_of_code::render;
+use std::path::PathBuf;
+use std::{fs, process};
+
+struct Args {
+    history_path: PathBuf,
+    html: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        history_path: args
+            .opt_value_from_str("--history-file")?
+            .unwrap_or_else(|| PathBuf::from(".timings/history.tsv")),
+        html: args.opt_value_from_str("--html")?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let history = match History::load(&args.history_path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!(
+                "Failed to read history \"{}\": {}. Run `cargo all --release -- --bench` to record one.",
+                args.history_path.display(),
+                e
+            );
+            process::exit(1);
+        }
+    };
+    let trends = history.trends();
+
+    match args.html {
+        Some(path) => match fs::write(&path, history::render_html(&trends)) {
+            Ok(_) => println!("🎄 Wrote report to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write report: {}", e);
+                process::exit(1);
+            }
+        },
+        None => print!("{}", history::render_text(&trends, render::mode())),
+    }
+}
@@ -3,8 +3,8 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
     process,
 };
 
@@ -40,6 +40,24 @@ mod tests {
 }
 "###;
 
+const BENCH_TEMPLATE: &str = r###"#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/DAY_PADDED.rs"]
+mod day;
+
+fn main() {
+    advent_of_code::bench::bench_solution(&advent_of_code::solution!(DAY, day));
+}
+"###;
+
+const BENCH_MANIFEST_TEMPLATE: &str = r###"
+[[bench]]
+name = "DAY_PADDED"
+harness = false
+bench = false
+"###;
+
+const SOLUTIONS_PATH: &str = "src/solutions.rs";
+
 fn parse_args() -> Result<u8, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     args.free_from_str()
@@ -50,7 +68,34 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn append_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().append(true).open(path)
+}
+
+/// Adds the day's module and `solution!` entry to the registry used by `cargo bench`.
+fn register_solution(day: u8, day_padded: &str) -> Result<(), io::Error> {
+    let registry = fs::read_to_string(SOLUTIONS_PATH)?;
+    let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+    let (modules, entries) = registry
+        .split_once("\npub const SOLUTIONS")
+        .ok_or_else(|| invalid("missing `SOLUTIONS`"))?;
+    let (entries, _) = entries
+        .rsplit_once("];")
+        .ok_or_else(|| invalid("missing end of `SOLUTIONS`"))?;
+
+    let registry = format!(
+        "{}#[path = \"bin/{}.rs\"]\nmod day{};\n\npub const SOLUTIONS{}    solution!({}, day{}),\n];\n",
+        modules, day_padded, day_padded, entries, day, day_padded
+    );
+    fs::write(SOLUTIONS_PATH, registry)
 }
 
 fn main() {
@@ -67,6 +112,7 @@ fn main() {
     let input_path = format!("src/inputs/{}.txt", day_padded);
     let example_path = format!("src/examples/{}.txt", day_padded);
     let module_path = format!("src/bin/{}.rs", day_padded);
+    let bench_path = format!("benches/{}.rs", day_padded);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -106,6 +152,52 @@ fn main() {
         }
     }
 
+    match safe_create_file(&bench_path).and_then(|mut file| {
+        file.write_all(
+            BENCH_TEMPLATE
+                .replace("DAY_PADDED", &day_padded)
+                .replace("DAY", &day.to_string())
+                .as_bytes(),
+        )
+    }) {
+        Ok(_) => {
+            println!("Created benchmark file \"{}\"", &bench_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to create benchmark file: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match append_file("Cargo.toml").and_then(|mut file| {
+        file.write_all(
+            BENCH_MANIFEST_TEMPLATE
+                .replace("DAY_PADDED", &day_padded)
+                .as_bytes(),
+        )
+    }) {
+        Ok(_) => {
+            println!(
+                "Added benchmark target \"{}\" to \"Cargo.toml\"",
+                &day_padded
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to add benchmark target: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match register_solution(day, &day_padded) {
+        Ok(_) => {
+            println!("Registered solution in \"{}\"", SOLUTIONS_PATH);
+        }
+        Err(e) => {
+            eprintln!("Failed to register solution: {}", e);
+            process::exit(1);
+        }
+    }
+
     println!("---");
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
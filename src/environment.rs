@@ -0,0 +1,127 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::env;
+use std::fmt;
+use std::fs;
+use std::thread;
+
+/// The cargo profile this binary was built with, `debug` or `release`.
+pub const PROFILE: &str = env!("AOC_PROFILE");
+pub const TARGET: &str = env!("AOC_TARGET");
+/// Version of the compiler this binary was built with.
+pub const RUSTC: &str = env!("AOC_RUSTC");
+
+/// The build and machine timings were taken on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub profile: String,
+    pub target: String,
+    pub rustc: String,
+    pub cpu: String,
+    /// Number of logical cores.
+    pub cores: usize,
+    pub hostname: String,
+}
+
+/// Reads the model name and the number of logical cores from `/proc/cpuinfo`.
+fn parse_cpuinfo(cpuinfo: &str) -> (Option<String>, usize) {
+    let model = cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string());
+    let cores = cpuinfo
+        .lines()
+        .filter(|line| line.split(':').next().map(str::trim) == Some("processor"))
+        .count();
+    (model, cores)
+}
+
+pub fn cpu_model() -> Option<String> {
+    parse_cpuinfo(&fs::read_to_string("/proc/cpuinfo").ok()?).0
+}
+
+fn hostname() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| fs::read_to_string("/etc/hostname"))
+        .ok()
+        .or_else(|| env::var("HOSTNAME").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+impl Environment {
+    /// Describes the current build and machine. Values that cannot be
+    /// determined are recorded as `unknown`.
+    pub fn collect() -> Environment {
+        let unknown = || String::from("unknown");
+        let (cpu, cores) = fs::read_to_string("/proc/cpuinfo")
+            .map(|cpuinfo| parse_cpuinfo(&cpuinfo))
+            .unwrap_or_default();
+
+        Environment {
+            profile: PROFILE.to_string(),
+            target: TARGET.to_string(),
+            rustc: RUSTC.to_string(),
+            cpu: cpu.unwrap_or_else(unknown),
+            cores: match cores {
+                0 => thread::available_parallelism().map_or(0, |n| n.get()),
+                cores => cores,
+            },
+            hostname: hostname().unwrap_or_else(unknown),
+        }
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self.profile == "debug"
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} build for {} on {}: {} ({} core{}), {}",
+            self.profile,
+            self.target,
+            self.hostname,
+            self.cpu,
+            self.cores,
+            if self.cores == 1 { "" } else { "s" },
+            self.rustc
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n\nprocessor\t: 1\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+        assert_eq!(
+            parse_cpuinfo(cpuinfo),
+            (Some(String::from("AMD Ryzen 7 5800X 8-Core Processor")), 2)
+        );
+        assert_eq!(parse_cpuinfo(""), (None, 0));
+    }
+
+    #[test]
+    fn test_display() {
+        let environment = Environment {
+            profile: String::from("release"),
+            target: String::from("x86_64-unknown-linux-gnu"),
+            rustc: String::from("rustc 1.95.0"),
+            cpu: String::from("AMD Ryzen 7 5800X"),
+            cores: 16,
+            hostname: String::from("elfbook"),
+        };
+        assert_eq!(
+            environment.to_string(),
+            "release build for x86_64-unknown-linux-gnu on elfbook: AMD Ryzen 7 5800X (16 cores), rustc 1.95.0"
+        );
+    }
+}
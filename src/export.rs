@@ -0,0 +1,234 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::allocations::AllocStats;
+use crate::bench::Stats;
+use crate::environment::Environment;
+use crate::runner::{DayResult, PartResult};
+use crate::tuning::Tuning;
+
+/// Quotes and escapes `value` as a JSON string.
+pub fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option<T>(value: Option<T>, to_json: impl FnOnce(T) -> String) -> String {
+    value.map(to_json).unwrap_or_else(|| String::from("null"))
+}
+
+fn nanos(duration: Duration) -> u128 {
+    duration.as_nanos()
+}
+
+fn stats_json(stats: &Stats) -> String {
+    format!(
+        r#"{{"samples": {}, "iterations": {}, "mean_ns": {}, "median_ns": {}, "std_dev_ns": {}, "min_ns": {}, "max_ns": {}}}"#,
+        stats.samples,
+        stats.iterations,
+        nanos(stats.mean),
+        nanos(stats.median),
+        nanos(stats.std_dev),
+        nanos(stats.min),
+        nanos(stats.max)
+    )
+}
+
+fn allocations_json(allocations: &AllocStats) -> String {
+    format!(
+        r#"{{"count": {}, "bytes": {}, "peak_bytes": {}}}"#,
+        allocations.count, allocations.bytes, allocations.peak
+    )
+}
+
+fn environment_json(environment: &Environment) -> String {
+    format!(
+        r#"{{"profile": {}, "target": {}, "rustc": {}, "cpu": {}, "cores": {}, "hostname": {}}}"#,
+        json_string(&environment.profile),
+        json_string(&environment.target),
+        json_string(&environment.rustc),
+        json_string(&environment.cpu),
+        environment.cores,
+        json_string(&environment.hostname)
+    )
+}
+
+fn tuning_json(tuning: &Tuning) -> String {
+    format!(
+        r#"{{"core": {}, "nice": {}, "governor": {}}}"#,
+        json_option(tuning.core, |core| core.to_string()),
+        json_option(tuning.nice, |nice| nice.to_string()),
+        json_option(tuning.governor.as_deref(), json_string)
+    )
+}
+
+fn part_json(part: &PartResult) -> String {
+    format!(
+        r#"{{"part": {}, "answer": {}, "expected": {}, "error": {}, "elapsed_ns": {}, "time_ns": {}, "stats": {}, "allocations": {}}}"#,
+        part.part,
+        json_option(part.answer.as_deref(), json_string),
+        json_option(part.expected.as_deref(), json_string),
+        json_option(part.error.as_ref(), |e| json_string(&e.to_string())),
+        nanos(part.elapsed),
+        nanos(part.time()),
+        json_option(part.stats.as_ref(), stats_json),
+        json_option(part.allocations.as_ref(), allocations_json)
+    )
+}
+
+fn slowest_json(part: &PartResult) -> String {
+    format!(
+        r#"{{"day": {}, "part": {}, "time_ns": {}}}"#,
+        part.day,
+        part.part,
+        nanos(part.time())
+    )
+}
+
+/// Serializes the runner's results as a JSON document.
+/// `slowest` is included as a separate list if given, see `filter::slowest`.
+pub fn to_json(
+    days: &[DayResult],
+    slowest: Option<&[PartResult]>,
+    environment: &Environment,
+    tuning: &Tuning,
+) -> String {
+    let mut out = format!(
+        "{{\n  \"environment\": {},\n  \"tuning\": {},\n  \"days\": [",
+        environment_json(environment),
+        tuning_json(tuning)
+    );
+    for (idx, day) in days.iter().enumerate() {
+        let parts = match &day.parts {
+            Some(parts) => format!(
+                "[\n        {}\n      ]",
+                parts
+                    .iter()
+                    .map(part_json)
+                    .collect::<Vec<_>>()
+                    .join(",\n        ")
+            ),
+            None => String::from("[]"),
+        };
+        write!(
+            out,
+            "{}\n    {{\n      \"day\": {},\n      \"input\": {},\n      \"parts\": {}\n    }}",
+            if idx == 0 { "" } else { "," },
+            day.day,
+            day.parts.is_some(),
+            parts
+        )
+        .unwrap();
+    }
+
+    let total = days
+        .iter()
+        .flat_map(|day| day.parts.iter().flatten())
+        .filter(|part| part.is_solved())
+        .map(|part| part.time())
+        .sum::<Duration>();
+    write!(out, "\n  ],\n  \"total_ns\": {}", nanos(total)).unwrap();
+    if let Some(slowest) = slowest {
+        write!(
+            out,
+            ",\n  \"slowest\": [{}]",
+            slowest
+                .iter()
+                .map(slowest_json)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+    }
+    out.push_str("\n}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartError;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let part = |part, answer: Option<&str>, error| PartResult {
+            day: 1,
+            part,
+            answer: answer.map(String::from),
+            elapsed: Duration::from_micros(5),
+            stats: None,
+            error,
+            allocations: Some(AllocStats {
+                count: 1,
+                bytes: 16,
+                peak: 16,
+            }),
+            expected: Some(String::from("42")),
+        };
+        let days = [
+            DayResult {
+                day: 1,
+                parts: Some(vec![
+                    part(1, Some("42"), None),
+                    part(2, None, Some(PartError::Panicked("oops".into()))),
+                ]),
+            },
+            DayResult {
+                day: 2,
+                parts: None,
+            },
+        ];
+
+        let environment = Environment {
+            profile: String::from("release"),
+            target: String::from("x86_64-unknown-linux-gnu"),
+            rustc: String::from("rustc 1.95.0"),
+            cpu: String::from("AMD Ryzen 7 5800X"),
+            cores: 16,
+            hostname: String::from("elfbook"),
+        };
+
+        let tuning = Tuning {
+            core: Some(2),
+            nice: None,
+            governor: Some(String::from("performance")),
+        };
+
+        let json = to_json(&days, None, &environment, &tuning);
+        assert!(json.starts_with(r#"{
+  "environment": {"profile": "release", "target": "x86_64-unknown-linux-gnu", "rustc": "rustc 1.95.0", "cpu": "AMD Ryzen 7 5800X", "cores": 16, "hostname": "elfbook"},
+  "tuning": {"core": 2, "nice": null, "governor": "performance"},
+  "days": ["#));
+        assert!(json.contains(r#"{"part": 1, "answer": "42", "expected": "42", "error": null, "elapsed_ns": 5000, "time_ns": 5000, "stats": null, "allocations": {"count": 1, "bytes": 16, "peak_bytes": 16}}"#));
+        assert!(json.contains(r#""error": "panicked: oops""#));
+        assert!(json.contains("\"day\": 2,\n      \"input\": false,\n      \"parts\": []"));
+        assert!(json.ends_with("\"total_ns\": 5000\n}\n"));
+
+        let slowest = [part(1, Some("42"), None)];
+        assert!(to_json(&days, Some(&slowest), &environment, &tuning).ends_with(
+            "\"total_ns\": 5000,\n  \"slowest\": [{\"day\": 1, \"part\": 1, \"time_ns\": 5000}]\n}\n"
+        ));
+    }
+}
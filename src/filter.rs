@@ -0,0 +1,269 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::cmp::Reverse;
+use std::ops::RangeInclusive;
+
+use crate::runner::{DayResult, PartResult};
+
+/// Selects which days and parts the runner runs and reports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Days to run, all days if empty.
+    pub days: Vec<RangeInclusive<u8>>,
+    /// Part to run, both parts if `None`.
+    pub part: Option<u8>,
+    /// Only report parts that produced an answer.
+    pub only_solved: bool,
+    /// Only report parts that failed or gave a wrong answer.
+    pub only_failing: bool,
+}
+
+/// Parses a day selection like `3-7,12`.
+pub fn parse_days(value: &str) -> Result<Vec<RangeInclusive<u8>>, String> {
+    let parse_day = |day: &str| match day.trim().parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("invalid day \"{}\", expected 1-25", day.trim())),
+    };
+
+    value
+        .split(',')
+        .map(|range| {
+            let (start, end) = match range.split_once('-') {
+                Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+                None => (parse_day(range)?, parse_day(range)?),
+            };
+            if start > end {
+                return Err(format!("invalid day range \"{}\"", range.trim()));
+            }
+            Ok(start..=end)
+        })
+        .collect()
+}
+
+/// Parses a part selection, either `1` or `2`.
+pub fn parse_part(value: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(part @ 1..=2) => Ok(part),
+        _ => Err(format!("invalid part \"{}\", expected 1 or 2", value)),
+    }
+}
+
+impl Filter {
+    pub fn includes_day(&self, day: u8) -> bool {
+        self.days.is_empty() || self.days.iter().any(|range| range.contains(&day))
+    }
+
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    /// Whether the day or part selectors leave anything out of the run.
+    /// Status filters do not count, they only change what is printed.
+    pub fn is_active(&self) -> bool {
+        !self.days.is_empty() || self.part.is_some()
+    }
+
+    fn reports(&self, result: &PartResult) -> bool {
+        (!self.only_solved || result.is_solved()) && (!self.only_failing || result.is_failing())
+    }
+
+    /// Describes the day and part selectors, e.g. `days 3-7,12, part 2`.
+    /// `None` if the run covers every part.
+    pub fn selection(&self) -> Option<String> {
+        let days = self
+            .days
+            .iter()
+            .map(|range| match (range.start(), range.end()) {
+                (start, end) if start == end => start.to_string(),
+                (start, end) => format!("{}-{}", start, end),
+            })
+            .collect::<Vec<_>>();
+        let selectors = [
+            (!days.is_empty()).then(|| format!("days {}", days.join(","))),
+            self.part.map(|part| format!("part {}", part)),
+        ];
+        let selection = selectors.into_iter().flatten().collect::<Vec<_>>();
+        (!selection.is_empty()).then(|| selection.join(", "))
+    }
+
+    /// Every part of `days` that the day and part selectors include. Status
+    /// filters only change what is printed, so they are ignored here.
+    pub fn selected(&self, days: &[DayResult]) -> Vec<PartResult> {
+        days.iter()
+            .filter(|day| self.includes_day(day.day))
+            .flat_map(|day| day.parts.iter().flatten())
+            .filter(|part| self.includes_part(part.part))
+            .cloned()
+            .collect()
+    }
+
+    /// Drops parts that should not be reported, and the day itself if none are left.
+    /// Days without input are only kept if no status filter is set.
+    pub fn apply(&self, day: &DayResult) -> Option<DayResult> {
+        match &day.parts {
+            None if self.only_solved || self.only_failing => None,
+            None => Some(day.clone()),
+            Some(parts) => {
+                let parts = parts
+                    .iter()
+                    .filter(|part| self.reports(part))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (!parts.is_empty()).then_some(DayResult {
+                    day: day.day,
+                    parts: Some(parts),
+                })
+            }
+        }
+    }
+}
+
+/// The `n` solved parts that took the longest, slowest first.
+pub fn slowest(results: &[PartResult], n: usize) -> Vec<PartResult> {
+    let mut solved = results
+        .iter()
+        .filter(|result| result.is_solved())
+        .cloned()
+        .collect::<Vec<_>>();
+    solved.sort_by_key(|result| Reverse(result.time()));
+    solved.truncate(n);
+    solved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::Baseline;
+    use crate::runner::PartError;
+    use crate::summary::total_time;
+    use std::time::Duration;
+
+    fn result(part: u8, micros: u64, answer: Option<&str>, error: Option<PartError>) -> PartResult {
+        PartResult {
+            day: 1,
+            part,
+            answer: answer.map(String::from),
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+            error,
+            allocations: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3-7,12"), Ok(vec![3..=7, 12..=12]));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("7-3").is_err());
+        assert!(parse_days("1-26").is_err());
+        assert!(parse_part("3").is_err());
+    }
+
+    #[test]
+    fn test_includes() {
+        let filter = Filter {
+            days: parse_days("3-7,12").unwrap(),
+            part: Some(2),
+            ..Filter::default()
+        };
+        assert!(filter.includes_day(5) && filter.includes_day(12));
+        assert!(!filter.includes_day(8));
+        assert!(filter.includes_part(2) && !filter.includes_part(1));
+        assert!(Filter::default().includes_day(25));
+        assert!(filter.is_active() && !Filter::default().is_active());
+        assert_eq!(filter.selection().as_deref(), Some("days 3-7,12, part 2"));
+        assert_eq!(Filter::default().selection(), None);
+        assert!(!Filter {
+            only_failing: true,
+            ..Filter::default()
+        }
+        .is_active());
+    }
+
+    #[test]
+    fn test_apply() {
+        let day = DayResult {
+            day: 1,
+            parts: Some(vec![
+                result(1, 10, Some("1"), None),
+                result(
+                    2,
+                    10,
+                    None,
+                    Some(PartError::TimedOut(Duration::from_secs(1))),
+                ),
+            ]),
+        };
+        let no_input = DayResult {
+            day: 2,
+            parts: None,
+        };
+
+        let failing = Filter {
+            only_failing: true,
+            ..Filter::default()
+        };
+        let parts = failing.apply(&day).unwrap().parts.unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].part, 2);
+        assert!(failing.apply(&no_input).is_none());
+
+        let solved = Filter {
+            only_solved: true,
+            ..Filter::default()
+        };
+        assert_eq!(solved.apply(&day).unwrap().parts.unwrap()[0].part, 1);
+        assert!(Filter::default().apply(&no_input).is_some());
+    }
+
+    #[test]
+    fn test_selected_ignores_status() {
+        let mut wrong = result(2, 30, Some("2"), None);
+        wrong.expected = Some(String::from("3"));
+        let days = [DayResult {
+            day: 1,
+            parts: Some(vec![result(1, 10, Some("1"), None), wrong]),
+        }];
+        let failing = Filter {
+            only_failing: true,
+            ..Filter::default()
+        };
+
+        // what gets saved, compared and budgeted does not depend on `--only-failing`.
+        assert_eq!(failing.apply(&days[0]).unwrap().parts.unwrap().len(), 1);
+        let selected = failing.selected(&days);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(
+            Baseline::from_results(&selected),
+            Baseline::from_results(&Filter::default().selected(&days))
+        );
+        assert_eq!(total_time(&selected), Duration::from_micros(40));
+
+        let second = Filter {
+            part: Some(2),
+            ..failing
+        };
+        assert_eq!(second.selected(&days)[0].part, 2);
+        assert!(Filter {
+            days: vec![2..=2],
+            ..Filter::default()
+        }
+        .selected(&days)
+        .is_empty());
+    }
+
+    #[test]
+    fn test_slowest() {
+        let results = [
+            result(1, 10, Some("1"), None),
+            result(2, 30, Some("2"), None),
+            result(1, 50, None, None),
+        ];
+        let slowest = slowest(&results, 5);
+        assert_eq!(slowest.len(), 2);
+        assert_eq!(slowest[0].part, 2);
+    }
+}
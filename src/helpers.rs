@@ -2,3 +2,27 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+pub mod bitset;
+pub mod cycle;
+pub mod grid;
+pub mod hex;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod rng;
+pub mod search;
+pub mod sparse;
+pub mod union_find;
+
+pub use bitset::{BitSet, BitSet128, BitSet64, LetterSet};
+pub use grid::Grid;
+pub use hex::{Hex, HexDirection};
+pub use interval::{Interval, IntervalSet};
+pub use math::BigUint;
+pub use memo::Memo;
+pub use point::{Direction, Point2, Point3};
+pub use rng::Rng;
+pub use sparse::SparseGrid;
+pub use union_find::UnionFind;
@@ -0,0 +1,622 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Sub};
+
+/// The indices of the set bits of a word, in ascending order.
+#[derive(Debug, Clone)]
+pub struct Bits {
+    word: u128,
+    base: usize,
+}
+
+impl Iterator for Bits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.word == 0 {
+            return None;
+        }
+        let index = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.base + index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.word.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+/// Defines a set of small integers stored in the bits of a single word.
+macro_rules! fixed_bitset {
+    ($(#[$doc:meta])* $name:ident, $word:ty) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name($word);
+
+        impl $name {
+            /// The number of integers the set can hold, `0..CAPACITY`.
+            pub const CAPACITY: usize = <$word>::BITS as usize;
+            pub const EMPTY: $name = $name(0);
+            pub const FULL: $name = $name(<$word>::MAX);
+
+            pub const fn new() -> $name {
+                $name::EMPTY
+            }
+
+            pub const fn from_bits(bits: $word) -> $name {
+                $name(bits)
+            }
+
+            pub const fn bits(self) -> $word {
+                self.0
+            }
+
+            /// Adds `index`, returns whether it was newly added.
+            /// Panics if `index` is not below `CAPACITY`.
+            pub fn insert(&mut self, index: usize) -> bool {
+                let bit = Self::bit(index);
+                let added = self.0 & bit == 0;
+                self.0 |= bit;
+                added
+            }
+
+            /// Removes `index`, returns whether it was present.
+            pub fn remove(&mut self, index: usize) -> bool {
+                let present = self.contains(index);
+                if present {
+                    self.0 &= !Self::bit(index);
+                }
+                present
+            }
+
+            pub fn contains(self, index: usize) -> bool {
+                index < Self::CAPACITY && self.0 & Self::bit(index) != 0
+            }
+
+            pub fn len(self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            pub fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// The smallest index in the set.
+            pub fn first(self) -> Option<usize> {
+                (!self.is_empty()).then(|| self.0.trailing_zeros() as usize)
+            }
+
+            /// The largest index in the set.
+            pub fn last(self) -> Option<usize> {
+                (!self.is_empty()).then(|| Self::CAPACITY - 1 - self.0.leading_zeros() as usize)
+            }
+
+            pub fn union(self, other: $name) -> $name {
+                $name(self.0 | other.0)
+            }
+
+            pub fn intersection(self, other: $name) -> $name {
+                $name(self.0 & other.0)
+            }
+
+            pub fn difference(self, other: $name) -> $name {
+                $name(self.0 & !other.0)
+            }
+
+            pub fn symmetric_difference(self, other: $name) -> $name {
+                $name(self.0 ^ other.0)
+            }
+
+            pub fn is_subset(self, other: $name) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            pub fn is_disjoint(self, other: $name) -> bool {
+                self.0 & other.0 == 0
+            }
+
+            /// The indices in the set in ascending order.
+            pub fn iter(self) -> Bits {
+                Bits {
+                    word: self.0 as u128,
+                    base: 0,
+                }
+            }
+
+            fn bit(index: usize) -> $word {
+                assert!(
+                    index < Self::CAPACITY,
+                    "index {} is out of range for {}",
+                    index,
+                    stringify!($name)
+                );
+                1 << index
+            }
+        }
+
+        impl BitOr for $name {
+            type Output = $name;
+
+            fn bitor(self, other: $name) -> $name {
+                self.union(other)
+            }
+        }
+
+        impl BitAnd for $name {
+            type Output = $name;
+
+            fn bitand(self, other: $name) -> $name {
+                self.intersection(other)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                self.difference(other)
+            }
+        }
+
+        impl BitXor for $name {
+            type Output = $name;
+
+            fn bitxor(self, other: $name) -> $name {
+                self.symmetric_difference(other)
+            }
+        }
+
+        impl BitOrAssign for $name {
+            fn bitor_assign(&mut self, other: $name) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl BitAndAssign for $name {
+            fn bitand_assign(&mut self, other: $name) {
+                self.0 &= other.0;
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = usize;
+            type IntoIter = Bits;
+
+            fn into_iter(self) -> Bits {
+                self.iter()
+            }
+        }
+
+        impl FromIterator<usize> for $name {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> $name {
+                let mut set = $name::new();
+                for index in iter {
+                    set.insert(index);
+                }
+                set
+            }
+        }
+
+        impl Extend<usize> for $name {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                for index in iter {
+                    self.insert(index);
+                }
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+    };
+}
+
+fixed_bitset!(
+    /// A set of integers below 64, backed by a `u64`.
+    BitSet64,
+    u64
+);
+fixed_bitset!(
+    /// A set of integers below 128, backed by a `u128`.
+    BitSet128,
+    u128
+);
+
+/// A set of integers of any size, backed by a vector of `u64` words that grows
+/// with the largest index. Trailing empty words are dropped, so equal sets compare equal.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    /// An empty set with room for `0..capacity` before it has to grow.
+    pub fn with_capacity(capacity: usize) -> BitSet {
+        BitSet {
+            words: Vec::with_capacity(capacity.div_ceil(64)),
+        }
+    }
+
+    /// Adds `index`, returns whether it was newly added.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes `index`, returns whether it was present.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let present = self.contains(index);
+        if present {
+            self.words[index / 64] &= !(1 << (index % 64));
+            self.trim();
+        }
+        present
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .is_some_and(|word| word & 1 << (index % 64) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// The smallest index in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// The largest index in the set.
+    pub fn last(&self) -> Option<usize> {
+        let word = self.words.last()?;
+        Some(self.words.len() * 64 - 1 - word.leading_zeros() as usize)
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut union = long.clone();
+        for (word, other) in union.words.iter_mut().zip(&short.words) {
+            *word |= other;
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        let mut difference = self.clone();
+        for (word, other) in difference.words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+        difference.trim();
+        difference
+    }
+
+    pub fn symmetric_difference(&self, other: &BitSet) -> BitSet {
+        let mut difference = self.union(other);
+        for (word, (a, b)) in difference
+            .words
+            .iter_mut()
+            .zip(self.words.iter().zip(&other.words))
+        {
+            *word = a ^ b;
+        }
+        difference.trim();
+        difference
+    }
+
+    pub fn is_subset(&self, other: &BitSet) -> bool {
+        self.words.len() <= other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &BitSet) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// The indices in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| Bits {
+            word: word as u128,
+            base: i * 64,
+        })
+    }
+
+    fn combine(&self, other: &BitSet, op: impl Fn(u64, u64) -> u64) -> BitSet {
+        let mut combined = BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+        };
+        combined.trim();
+        combined
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitSet {
+        let mut set = BitSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for index in iter {
+            self.insert(index);
+        }
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A set of the ASCII letters `a-z` and `A-Z`, stored at their puzzle priority:
+/// `a` to `z` are 1 to 26, `A` to `Z` are 27 to 52.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LetterSet(BitSet64);
+
+impl LetterSet {
+    pub const fn new() -> LetterSet {
+        LetterSet(BitSet64::EMPTY)
+    }
+
+    /// The priority of an ASCII letter, `None` for any other character.
+    pub fn priority(letter: char) -> Option<usize> {
+        match letter {
+            'a'..='z' => Some(letter as usize - 'a' as usize + 1),
+            'A'..='Z' => Some(letter as usize - 'A' as usize + 27),
+            _ => None,
+        }
+    }
+
+    /// The letter with the given priority, see `priority`.
+    pub fn letter(priority: usize) -> Option<char> {
+        match priority {
+            1..=26 => Some((b'a' + priority as u8 - 1) as char),
+            27..=52 => Some((b'A' + priority as u8 - 27) as char),
+            _ => None,
+        }
+    }
+
+    /// Adds `letter`, returns whether it was newly added. Characters that are
+    /// not ASCII letters are never added, like `remove` and `contains` ignore them.
+    pub fn insert(&mut self, letter: char) -> bool {
+        LetterSet::priority(letter).is_some_and(|priority| self.0.insert(priority))
+    }
+
+    pub fn remove(&mut self, letter: char) -> bool {
+        LetterSet::priority(letter).is_some_and(|priority| self.0.remove(priority))
+    }
+
+    pub fn contains(self, letter: char) -> bool {
+        LetterSet::priority(letter).is_some_and(|priority| self.0.contains(priority))
+    }
+
+    pub fn len(self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The letters in the set, lowercase before uppercase.
+    pub fn letters(self) -> impl Iterator<Item = char> {
+        self.0.iter().filter_map(LetterSet::letter)
+    }
+
+    /// The priorities of the letters in the set in ascending order.
+    pub fn priorities(self) -> Bits {
+        self.0.iter()
+    }
+}
+
+/// Collects the letters of `text`, skipping digits, whitespace and other characters.
+impl From<&str> for LetterSet {
+    fn from(text: &str) -> LetterSet {
+        text.chars().collect()
+    }
+}
+
+impl FromIterator<char> for LetterSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> LetterSet {
+        let mut set = LetterSet::new();
+        for letter in iter {
+            set.insert(letter);
+        }
+        set
+    }
+}
+
+impl BitOr for LetterSet {
+    type Output = LetterSet;
+
+    fn bitor(self, other: LetterSet) -> LetterSet {
+        LetterSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for LetterSet {
+    type Output = LetterSet;
+
+    fn bitand(self, other: LetterSet) -> LetterSet {
+        LetterSet(self.0 & other.0)
+    }
+}
+
+impl Sub for LetterSet {
+    type Output = LetterSet;
+
+    fn sub(self, other: LetterSet) -> LetterSet {
+        LetterSet(self.0 - other.0)
+    }
+}
+
+impl fmt::Debug for LetterSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.letters()).finish()
+    }
+}
+
+impl fmt::Display for LetterSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.letters()
+            .try_for_each(|letter| write!(f, "{}", letter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::Rng;
+    use std::collections::BTreeSet;
+
+    fn random_indices(rng: &mut Rng, max: usize) -> BTreeSet<usize> {
+        let count = rng.range(0..max / 2);
+        (0..count).map(|_| rng.range(0..max)).collect()
+    }
+
+    #[test]
+    fn test_fixed_bitset() {
+        let mut set = BitSet64::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        set.insert(63);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 63]);
+        assert_eq!((set.first(), set.last(), set.len()), (Some(3), Some(63), 2));
+        assert!(set.remove(3));
+        assert!(!set.contains(3) && !set.contains(200));
+        assert_eq!(format!("{:?}", set), "{63}");
+
+        let set = [0, 100, 127].into_iter().collect::<BitSet128>();
+        assert_eq!(set.last(), Some(127));
+        assert_eq!(BitSet128::FULL.len(), 128);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_fixed_bitset_capacity() {
+        BitSet64::new().insert(64);
+    }
+
+    #[test]
+    fn test_bitsets_against_btreeset() {
+        let mut rng = Rng::new(48);
+        for _ in 0..500 {
+            let (a, b) = (random_indices(&mut rng, 128), random_indices(&mut rng, 128));
+            let expected = [
+                a.union(&b).copied().collect::<Vec<_>>(),
+                a.intersection(&b).copied().collect(),
+                a.difference(&b).copied().collect(),
+                a.symmetric_difference(&b).copied().collect(),
+            ];
+
+            let (x, y) = (
+                a.iter().copied().collect::<BitSet128>(),
+                b.iter().copied().collect::<BitSet128>(),
+            );
+            let fixed = [x | y, x & y, x - y, x ^ y].map(|set| set.iter().collect::<Vec<_>>());
+            assert_eq!(fixed, expected);
+            assert_eq!(x.is_subset(y), a.is_subset(&b));
+            assert_eq!(x.is_disjoint(y), a.is_disjoint(&b));
+
+            // the growable set, with indices spread over several words.
+            let (x, y) = (
+                a.iter().map(|i| i * 3).collect::<BitSet>(),
+                b.iter().map(|i| i * 3).collect::<BitSet>(),
+            );
+            let growable = [
+                x.union(&y),
+                x.intersection(&y),
+                x.difference(&y),
+                x.symmetric_difference(&y),
+            ]
+            .map(|set| set.iter().map(|i| i / 3).collect::<Vec<_>>());
+            assert_eq!(growable, expected);
+            assert_eq!(x.is_subset(&y), a.is_subset(&b));
+            assert_eq!(x.is_disjoint(&y), a.is_disjoint(&b));
+            assert_eq!(x.len(), a.len());
+            assert_eq!(x.last(), a.last().map(|i| i * 3));
+        }
+    }
+
+    #[test]
+    fn test_growable_bitset_equality() {
+        let mut set = [1, 1000].into_iter().collect::<BitSet>();
+        set.remove(1000);
+        assert_eq!(set, [1].into_iter().collect());
+        assert_eq!(set.intersection(&BitSet::new()), BitSet::new());
+        assert!(set.contains(1) && !set.contains(1000));
+    }
+
+    #[test]
+    fn test_letter_set() {
+        let first = LetterSet::from("vJrwpWtwJgWr");
+        let second = LetterSet::from("hcsFMMfFFhFp");
+        let shared = first & second;
+        assert_eq!(shared.letters().collect::<Vec<_>>(), ['p']);
+        assert_eq!(shared.priorities().collect::<Vec<_>>(), [16]);
+        assert_eq!(LetterSet::priority('L'), Some(38));
+        assert_eq!(LetterSet::letter(38), Some('L'));
+        assert_eq!(LetterSet::from("zaZA").to_string(), "azAZ");
+        assert!(!first.contains('1'));
+    }
+
+    #[test]
+    fn test_letter_set_mixed_input() {
+        let mut set = LetterSet::from("a1 b\r\nc-é");
+        assert_eq!(set.to_string(), "abc");
+        assert!(!set.insert('7'));
+        assert!(set.insert('D') && !set.insert('D'));
+        assert_eq!(set.len(), 4);
+        assert_eq!("x y".chars().collect::<LetterSet>().to_string(), "xy");
+    }
+}
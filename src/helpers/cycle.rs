@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The cycle in the sequence of states `x0, f(x0), f(f(x0)), ...`: the state
+/// at index `start + length` is the first to repeat, it equals the one at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first index with the same state as `index`.
+    pub fn reduce(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Every state up to the first repetition, as recorded by `find_cycle`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> Simulation<S> {
+    /// The state after `index` steps, which may be far beyond the cycle.
+    pub fn state_at(&self, index: usize) -> &S {
+        &self.states[self.cycle.reduce(index)]
+    }
+
+    /// States before the first repetition, starting with the initial one.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Steps from `initial` until a state repeats, remembering every state in a hash map.
+/// The state space must be finite, or this never returns.
+pub fn find_cycle<S: Hash + Eq + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Simulation<S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Simulation {
+                cycle: Cycle {
+                    start,
+                    length: states.len() - start,
+                },
+                states,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Brent's algorithm, which only compares states and keeps two of them in memory,
+/// at the cost of calling `step` a few times more than `find_cycle`.
+/// The state space must be finite, or this never returns.
+pub fn brent<S: Eq + Clone>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by moving the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // a hare `length` steps ahead meets the tortoise at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// The state after `index` steps, simulating no more than `cycle.start + cycle.length` steps.
+pub fn state_at<S: Clone>(
+    initial: &S,
+    mut step: impl FnMut(&S) -> S,
+    cycle: Cycle,
+    index: usize,
+) -> S {
+    (0..cycle.reduce(index)).fold(initial.clone(), |state, _| step(&state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    fn naive_state_at(index: usize) -> u64 {
+        (0..index).fold(3, |x, _| step(&x))
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let simulation = find_cycle(3, step);
+        let Cycle { start, length } = simulation.cycle;
+
+        assert_eq!(simulation.states().len(), start + length);
+        assert_eq!(naive_state_at(start), naive_state_at(start + length));
+        assert!((0..start + length)
+            .all(|i| (i + 1..start + length).all(|j| naive_state_at(i) != naive_state_at(j))));
+
+        for index in [0, 5, 100, 12_345] {
+            assert_eq!(*simulation.state_at(index), naive_state_at(index));
+        }
+        let reduced = simulation.cycle.reduce(1_000_000_000);
+        assert!(reduced < start + length);
+        assert_eq!(*simulation.state_at(1_000_000_000), naive_state_at(reduced));
+    }
+
+    #[test]
+    fn test_brent() {
+        for initial in 0..50 {
+            let cycle = brent(&initial, step);
+            assert_eq!(cycle, find_cycle(initial, step).cycle);
+            assert_eq!(
+                state_at(&initial, step, cycle, 10_000),
+                (0..10_000).fold(initial, |x, _| step(&x))
+            );
+        }
+
+        // a cycle of length one right at the start.
+        assert_eq!(
+            brent(&0, |_| 0),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use super::point::Point2;
+
+/// A `(row, col)` position in a grid, `(0, 0)` is the top left cell.
+pub type Position = (usize, usize);
+
+/// A `(row, col)` offset to move by, e.g. `(-1, 0)` for up.
+/// A `Direction` converts into a step.
+pub type Step = (isize, isize);
+
+pub const STEPS_4: [Step; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const STEPS_8: [Step; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows that must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "row {} has {} cells, expected {}",
+                    idx + 1,
+                    row.len(),
+                    width
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character grid, one row per line, converting every character with `cell`.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> T) -> Result<Grid<T>, String> {
+        let mut cell = cell;
+        Grid::from_rows(
+            input
+                .lines()
+                .map(|line| line.chars().map(&mut cell).collect())
+                .collect(),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// Like `get`, for a point with `x` as the column and `y` as the row.
+    pub fn get_point(&self, point: Point2) -> Option<&T> {
+        self.get(point.to_position()?)
+    }
+
+    /// Moves from `position` by `step`, `None` if that leaves the grid.
+    pub fn offset(&self, (row, col): Position, step: impl Into<Step>) -> Option<Position> {
+        let (d_row, d_col) = step.into();
+        let position = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a width of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `col`, top to bottom.
+    ///
+    /// Panics if `col` is outside the grid, like `row` does.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.width,
+            "column {} is outside a grid of width {}",
+            col,
+            self.width
+        );
+        self.cells[col..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Cells from `start` in steps of `step` until the edge, including `start`.
+    pub fn line(
+        &self,
+        start: Position,
+        step: impl Into<Step>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let step = step.into();
+        std::iter::successors(self.contains(start).then_some(start), move |&position| {
+            self.offset(position, step)
+        })
+        .map(|position| (position, &self[position]))
+    }
+
+    /// Cells seen when looking from `start` in the direction of `step`,
+    /// nearest first and excluding `start`.
+    pub fn ray(
+        &self,
+        start: Position,
+        step: impl Into<Step>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.line(start, step).skip(1)
+    }
+
+    /// Diagonals running down and to the right, starting at the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .rev()
+            .map(|col| (0, col))
+            .chain((1..self.height).map(|row| (row, 0)));
+        starts.map(|start| self.line(start, (1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// Diagonals running down and to the left, starting at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_col = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|col| (0, col))
+            .chain((1..self.height).map(move |row| (row, last_col)));
+        starts.map(|start| self.line(start, (1, -1)).map(|(_, cell)| cell))
+    }
+
+    /// The up to four orthogonal neighbours of `position` inside the grid.
+    pub fn neighbours_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        STEPS_4
+            .iter()
+            .filter_map(move |&step| self.offset(position, step))
+    }
+
+    /// The up to eight orthogonal and diagonal neighbours of `position` inside the grid.
+    pub fn neighbours_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        STEPS_8
+            .iter()
+            .filter_map(move |&step| self.offset(position, step))
+    }
+
+    /// Converts every cell with `f`, keeping the layout.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid of `width` columns and `height` rows filled with `value`. Note
+    /// the order: it is the opposite of the `(row, col)` of positions.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T> {
+        self.rearrange(self.height, self.width, |(row, col)| (col, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let height = self.height;
+        self.rearrange(height, self.width, |(row, col)| (height - 1 - col, row))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        let width = self.width;
+        self.rearrange(self.height, width, |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Builds a `width` by `height` grid, taking each cell from `source(position)` in `self`.
+    fn rearrange(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(Position) -> Position,
+    ) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {:?} is outside of the {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {:?} is outside of the {}x{} grid",
+                position, width, height
+            ),
+        }
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &T {
+        match point.to_position() {
+            Some(position) => &self[position],
+            None => panic!("point {} is outside of the grid", point),
+        }
+    }
+}
+
+/// Renders the cells of each row next to each other, one row per line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::point::Direction;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", |c| c).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_access() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert!(Grid::parse("ab\nc", |c| c).is_err());
+
+        let mut grid = grid;
+        grid[(0, 0)] = 'x';
+        assert_eq!(grid.to_string(), "xbc\ndef");
+
+        let mut filled = Grid::new(3, 2, '.');
+        assert_eq!((filled.width(), filled.height()), (3, 2));
+        filled[(1, 2)] = '#';
+        assert_eq!(filled.to_string(), "...\n..#");
+        assert_eq!(filled.get((2, 0)), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(collect(grid.columns()), ["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), ["c", "bf", "ae", "d"]);
+        assert_eq!(collect(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+        assert_eq!(Grid::<char>::parse("", |c| c).unwrap().columns().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside a grid of width 3")]
+    fn test_column_out_of_bounds() {
+        let _ = grid().column(3);
+    }
+
+    #[test]
+    fn test_neighbours_and_rays() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 5);
+        assert_eq!(
+            grid.ray((0, 0), (0, 1))
+                .map(|(_, &c)| c)
+                .collect::<String>(),
+            "bc"
+        );
+        assert_eq!(
+            grid.ray((1, 2), (-1, -1))
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            [(0, 1)]
+        );
+        assert_eq!(grid.ray((1, 2), (1, 0)).count(), 0);
+        assert_eq!(
+            grid.ray((1, 0), Direction::Up).collect::<Vec<_>>(),
+            [((0, 0), &'a')]
+        );
+        assert_eq!(grid[Point2::new(2, 0)], 'c');
+        assert_eq!(grid.get_point(Point2::new(0, -1)), None);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -0,0 +1,150 @@
+use std::ops::{Add, AddAssign, Sub};
+
+/// A hexagon in axial coordinates on a grid of pointy-topped hexagons,
+/// the third cube coordinate is `s = -q - r`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// The six neighbours of a pointy-topped hexagon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub const fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r }
+    }
+
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// The number of steps between two hexagons.
+    pub fn distance(self, other: Hex) -> i64 {
+        let diff = self - other;
+        (diff.q.abs() + diff.r.abs() + diff.s().abs()) / 2
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        HexDirection::ALL
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+}
+
+impl HexDirection {
+    /// All directions, clockwise starting with `East`.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    pub fn turn_right(self) -> HexDirection {
+        HexDirection::ALL[(self as usize + 1) % 6]
+    }
+
+    pub fn turn_left(self) -> HexDirection {
+        HexDirection::ALL[(self as usize + 5) % 6]
+    }
+
+    pub fn opposite(self) -> HexDirection {
+        HexDirection::ALL[(self as usize + 3) % 6]
+    }
+
+    pub fn delta(self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::SouthEast => Hex::new(0, 1),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::NorthEast => Hex::new(1, -1),
+        }
+    }
+}
+
+/// Accepts the lowercase abbreviations used in puzzles, e.g. `se` or `w`.
+impl TryFrom<&str> for HexDirection {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<HexDirection, String> {
+        match value {
+            "e" => Ok(HexDirection::East),
+            "se" => Ok(HexDirection::SouthEast),
+            "sw" => Ok(HexDirection::SouthWest),
+            "w" => Ok(HexDirection::West),
+            "nw" => Ok(HexDirection::NorthWest),
+            "ne" => Ok(HexDirection::NorthEast),
+            _ => Err(format!("invalid hex direction \"{}\"", value)),
+        }
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Add<HexDirection> for Hex {
+    type Output = Hex;
+
+    fn add(self, direction: HexDirection) -> Hex {
+        self + direction.delta()
+    }
+}
+
+impl AddAssign<HexDirection> for Hex {
+    fn add_assign(&mut self, direction: HexDirection) {
+        *self = *self + direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        let mut hex = Hex::ORIGIN;
+        for direction in ["e", "e", "ne", "se"] {
+            hex += HexDirection::try_from(direction).unwrap();
+        }
+        assert_eq!(hex, Hex::new(3, 0));
+        assert_eq!(hex.distance(Hex::ORIGIN), 3);
+        assert_eq!(Hex::new(1, -1).distance(Hex::new(-1, 1)), 2);
+
+        assert!(Hex::ORIGIN
+            .neighbours()
+            .all(|n| n.distance(Hex::ORIGIN) == 1));
+        for direction in HexDirection::ALL {
+            assert_eq!(Hex::ORIGIN + direction + direction.opposite(), Hex::ORIGIN);
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::fmt;
+
+/// The half-open range of integers `start..end`, empty if `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// `start..end`, excluding `end`.
+    pub const fn new(start: i64, end: i64) -> Interval {
+        Interval { start, end }
+    }
+
+    /// `start..=end`, including `end`.
+    ///
+    /// Panics if `end` is `i64::MAX`, the exclusive end would not fit.
+    pub const fn inclusive(start: i64, end: i64) -> Interval {
+        Interval {
+            start,
+            end: end
+                .checked_add(1)
+                .expect("inclusive end must be below i64::MAX"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// The number of integers covered.
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start).max(0) as u64
+    }
+
+    /// The last integer covered, for use with `inclusive`.
+    pub fn last(&self) -> i64 {
+        self.end - 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every integer of `other` is covered, always true for an empty `other`.
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether both intervals cover a common integer.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Whether the union of both intervals is a single interval, i.e. they
+    /// overlap or one starts right where the other ends.
+    pub fn touches(&self, other: &Interval) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start <= other.end && other.start <= self.end
+    }
+
+    /// The common part, possibly empty.
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The union of both intervals, `None` if there is a gap between them.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.is_empty() {
+            Some(*other)
+        } else if other.is_empty() {
+            Some(*self)
+        } else {
+            self.touches(other)
+                .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+        }
+    }
+
+    /// The parts of `self` not covered by `other`, at most two non-empty intervals.
+    pub fn difference(&self, other: &Interval) -> impl Iterator<Item = Interval> {
+        let parts = if self.overlaps(other) {
+            [
+                Interval::new(self.start, other.start.min(self.end)),
+                Interval::new(other.end.max(self.start), self.end),
+            ]
+        } else {
+            [*self, Interval::new(0, 0)]
+        };
+        parts.into_iter().filter(|part| !part.is_empty())
+    }
+
+    pub fn iter(&self) -> std::ops::Range<i64> {
+        self.start..self.end
+    }
+}
+
+impl From<std::ops::Range<i64>> for Interval {
+    fn from(range: std::ops::Range<i64>) -> Interval {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl From<std::ops::RangeInclusive<i64>> for Interval {
+    fn from(range: std::ops::RangeInclusive<i64>) -> Interval {
+        Interval::inclusive(*range.start(), *range.end())
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted, non-overlapping and non-adjacent intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    /// Adds `interval`, merging it with every interval it touches.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        // intervals before `first` end before `interval` starts, those from `last` on start after it.
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| merged.union(i).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Removes every integer of `interval` from the set.
+    pub fn remove(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        let last = self.intervals.partition_point(|i| i.start < interval.end);
+        let rest = self.intervals[first..last]
+            .iter()
+            .flat_map(|i| i.difference(&interval))
+            .collect::<Vec<_>>();
+        self.intervals.splice(first..last, rest);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Whether every integer of `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval) -> bool {
+        interval.is_empty()
+            || self
+                .find(interval.start)
+                .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    /// Whether any integer of `interval` is in the set.
+    pub fn overlaps(&self, interval: &Interval) -> bool {
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        self.intervals
+            .get(first)
+            .is_some_and(|i| i.overlaps(interval))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = vec![];
+        let (mut a, mut b) = (0, 0);
+        while let (Some(left), Some(right)) = (self.intervals.get(a), other.intervals.get(b)) {
+            let common = left.intersection(right);
+            if !common.is_empty() {
+                intersection.push(common);
+            }
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet {
+            intervals: intersection,
+        }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        for &interval in &other.intervals {
+            difference.remove(interval);
+        }
+        difference
+    }
+
+    fn find(&self, value: i64) -> Option<&Interval> {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).filter(|i| i.contains(value))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::Rng;
+
+    /// Sets are checked against bitmaps of the integers `0..64`.
+    fn bitmap(interval: &Interval) -> u64 {
+        interval.iter().fold(0, |bits, value| bits | 1 << value)
+    }
+
+    fn set_bitmap(set: &IntervalSet) -> u64 {
+        set.intervals().iter().map(bitmap).fold(0, |a, b| a | b)
+    }
+
+    fn random_interval(rng: &mut Rng) -> Interval {
+        let start = rng.range(0..64) as i64;
+        Interval::new(start, start + rng.range(0..12) as i64).intersection(&Interval::new(0, 64))
+    }
+
+    fn random_set(rng: &mut Rng) -> IntervalSet {
+        let count = rng.range(0..6);
+        (0..count).map(|_| random_interval(rng)).collect()
+    }
+
+    fn is_normalized(set: &IntervalSet) -> bool {
+        set.intervals().iter().all(|i| !i.is_empty())
+            && set.intervals().windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    #[should_panic(expected = "inclusive end must be below i64::MAX")]
+    fn test_inclusive_overflow() {
+        Interval::inclusive(0, i64::MAX);
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::inclusive(2, 8);
+        let b = Interval::from(6..12);
+        assert_eq!(a, Interval::new(2, 9));
+        assert_eq!((a.len(), a.last()), (7, 8));
+        assert_eq!(a.intersection(&b), Interval::new(6, 9));
+        assert_eq!(a.union(&b), Some(Interval::new(2, 12)));
+        assert_eq!(a.union(&Interval::new(9, 10)), Some(Interval::new(2, 10)));
+        assert_eq!(a.union(&Interval::new(10, 11)), None);
+        assert_eq!(
+            a.difference(&Interval::new(4, 6)).collect::<Vec<_>>(),
+            [Interval::new(2, 4), Interval::new(6, 9)]
+        );
+        assert!(a.contains_interval(&Interval::inclusive(3, 8)));
+        assert!(!a.overlaps(&Interval::new(9, 12)));
+        assert_eq!(Interval::new(5, 3).len(), 0);
+    }
+
+    #[test]
+    fn test_interval_against_bitmap() {
+        let mut rng = Rng::new(44);
+        for _ in 0..2000 {
+            let (a, b) = (random_interval(&mut rng), random_interval(&mut rng));
+            let (bits_a, bits_b) = (bitmap(&a), bitmap(&b));
+
+            assert_eq!(a.len(), bits_a.count_ones() as u64);
+            assert_eq!(bitmap(&a.intersection(&b)), bits_a & bits_b);
+            assert_eq!(a.overlaps(&b), bits_a & bits_b != 0);
+            assert_eq!(a.contains_interval(&b), bits_b & !bits_a == 0);
+            assert_eq!(
+                a.difference(&b).map(|i| bitmap(&i)).fold(0, |x, y| x | y),
+                bits_a & !bits_b
+            );
+            if let Some(union) = a.union(&b) {
+                assert_eq!(bitmap(&union), bits_a | bits_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interval_set_against_bitmap() {
+        let mut rng = Rng::new(4);
+        for _ in 0..2000 {
+            let (a, b) = (random_set(&mut rng), random_set(&mut rng));
+            let (bits_a, bits_b) = (set_bitmap(&a), set_bitmap(&b));
+
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                assert!(is_normalized(&set), "{:?}", set);
+            }
+            assert_eq!(set_bitmap(&a.union(&b)), bits_a | bits_b);
+            assert_eq!(set_bitmap(&a.intersection(&b)), bits_a & bits_b);
+            assert_eq!(set_bitmap(&a.difference(&b)), bits_a & !bits_b);
+            assert_eq!(a.len(), bits_a.count_ones() as u64);
+
+            let interval = random_interval(&mut rng);
+            let bits = bitmap(&interval);
+            assert_eq!(a.overlaps(&interval), bits_a & bits != 0);
+            assert_eq!(a.contains_interval(&interval), bits & !bits_a == 0);
+            let value = rng.range(0..64) as i64;
+            assert_eq!(a.contains(value), bits_a & 1 << value != 0);
+        }
+    }
+
+    #[test]
+    fn test_merges_adjacent() {
+        let set = [
+            Interval::new(0, 3),
+            Interval::new(5, 7),
+            Interval::new(3, 5),
+        ]
+        .into_iter()
+        .collect::<IntervalSet>();
+        assert_eq!(set.intervals(), [Interval::new(0, 7)]);
+    }
+}
@@ -0,0 +1,408 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Rem};
+
+/// The greatest common divisor of two non-negative integers, `gcd(0, 0)` is 0.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + From<u8> + Rem<Output = T>,
+{
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of two non-negative integers, 0 if either is 0.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + From<u8> + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    if a == T::from(0) || b == T::from(0) {
+        T::from(0)
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// The greatest common divisor of all `values`, 0 for none.
+pub fn gcd_all<T>(values: impl IntoIterator<Item = T>) -> T
+where
+    T: Copy + PartialEq + From<u8> + Rem<Output = T>,
+{
+    values.into_iter().fold(T::from(0), gcd)
+}
+
+/// The least common multiple of all `values`, e.g. of the cycle lengths of
+/// several simulations. 1 for none.
+pub fn lcm_all<T>(values: impl IntoIterator<Item = T>) -> T
+where
+    T: Copy + PartialEq + From<u8> + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    values.into_iter().fold(T::from(1), lcm)
+}
+
+/// `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1`, `None` if `a` and `modulus` are not coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd_wide((a as i128).rem_euclid(modulus as i128), modulus as i128);
+    (g == 1).then(|| x.rem_euclid(modulus as i128) as i64)
+}
+
+/// `base` to the power of `exponent`, modulo `modulus`, by repeated squaring.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// Solves `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs with the
+/// Chinese remainder theorem. The moduli do not have to be coprime. Returns the
+/// smallest non-negative `x` together with the combined modulus, their least
+/// common multiple; `None` if the congruences contradict each other or the
+/// combined modulus does not fit into an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0_i128, 1_i128);
+    for (residue, modulus) in congruences {
+        let modulus = modulus as i128;
+        let residue = (residue as i128).rem_euclid(modulus);
+        let (g, p, _) = extended_gcd_wide(m, modulus);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+        // x + m * t satisfies both congruences for t ≡ (difference / g) * p (mod modulus / g).
+        let step = modulus / g;
+        let t = (difference / g % step * p % step).rem_euclid(step);
+        x += m * t;
+        m = m.checked_mul(step).filter(|&m| m <= i64::MAX as i128)?;
+        x = x.rem_euclid(m);
+    }
+    Some((x as i64, m as i64))
+}
+
+/// The largest integer whose square is at most `n`.
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+pub fn is_square(n: u64) -> bool {
+    let root = n.isqrt();
+    root * root == n
+}
+
+/// An unsigned integer of any size, for answers that overflow `u128`.
+/// Supports addition and multiplication and prints in decimal, so parts may return it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Base 10^9 digits, least significant first, without leading zeros.
+    limbs: Vec<u32>,
+}
+
+const BASE: u64 = 1_000_000_000;
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint::default()
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from(1_u64)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// The value, if it fits into a `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        self.limbs.iter().rev().try_fold(0_u128, |value, &limb| {
+            value.checked_mul(BASE as u128)?.checked_add(limb as u128)
+        })
+    }
+
+    fn trim(mut self) -> BigUint {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> BigUint {
+        let mut limbs = vec![];
+        while value > 0 {
+            limbs.push((value % BASE as u128) as u32);
+            value /= BASE as u128;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::from(value as u128)
+    }
+}
+
+impl From<usize> for BigUint {
+    fn from(value: usize) -> BigUint {
+        BigUint::from(value as u128)
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = carry
+                + *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64;
+            limbs.push((sum % BASE) as u32);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        BigUint { limbs }
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0_u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = limbs[i + j] + a as u64 * b as u64 + carry;
+                limbs[i + j] = product % BASE;
+                carry = product / BASE;
+            }
+            limbs[i + other.limbs.len()] += carry;
+        }
+        BigUint {
+            limbs: limbs.into_iter().map(|limb| limb as u32).collect(),
+        }
+        .trim()
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        &self + &other
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        *self = &*self + other;
+    }
+}
+
+impl MulAssign<&BigUint> for BigUint {
+    fn mul_assign(&mut self, other: &BigUint) {
+        *self = &*self * other;
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        iter.fold(BigUint::zero(), |sum, value| &sum + &value)
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        iter.fold(BigUint::one(), |product, value| &product * &value)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((most, rest)) = self.limbs.split_last() else {
+            return f.pad("0");
+        };
+        let digits = rest.iter().rev().fold(most.to_string(), |digits, limb| {
+            format!("{}{:09}", digits, limb)
+        });
+        f.pad(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::Rng;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12_u64, 18), 6);
+        assert_eq!(gcd(0_u32, 7), 7);
+        assert_eq!(lcm(4_usize, 6), 12);
+        assert_eq!(lcm(0_i64, 6), 0);
+        assert_eq!(gcd_all([24_u64, 36, 60]), 12);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd_and_inverse() {
+        let mut rng = Rng::new(49);
+        for _ in 0..1000 {
+            let (a, b) = (rng.range(0..10_000) as i64, rng.range(1..10_000) as i64);
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+
+            match mod_inverse(a, b) {
+                Some(inverse) => {
+                    assert!((0..b).contains(&inverse));
+                    assert_eq!(a * inverse % b, 1 % b);
+                }
+                None => assert_ne!(g, 1),
+            }
+        }
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        // Fermat's little theorem with a modulus close to u64::MAX.
+        let prime = 18_446_744_073_709_551_557;
+        assert_eq!(mod_pow(12_345, prime - 1, prime), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli sharing factors.
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(0, 1 << 40), (1, (1 << 40) - 1), (0, 3 << 40)]), None);
+
+        let mut rng = Rng::new(4);
+        for _ in 0..500 {
+            let congruences = (0..rng.range(1..4))
+                .map(|_| {
+                    let modulus = rng.range(1..30) as i64;
+                    (rng.range(0..100) as i64, modulus)
+                })
+                .collect::<Vec<_>>();
+            let brute_force = (0..lcm_all(congruences.iter().map(|&(_, m)| m)))
+                .find(|x| congruences.iter().all(|&(r, m)| x % m == r % m));
+            assert_eq!(crt(congruences.clone()).map(|(x, _)| x), brute_force);
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert!(is_square(144) && !is_square(145));
+    }
+
+    #[test]
+    fn test_big_uint() {
+        let factorial = (1..=30_u64).map(BigUint::from).product::<BigUint>();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(
+            BigUint::from(2_u64).pow(128).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(BigUint::from(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(BigUint::from(2_u64).pow(128).to_u128(), None);
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(format!("{:>4}", BigUint::from(7_u64)), "   7");
+
+        let mut rng = Rng::new(9);
+        for _ in 0..500 {
+            let (a, b) = (rng.next_u64() as u128, rng.next_u64() as u128);
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!((&x * &y).to_u128(), Some(a * b));
+            assert_eq!((&x + &y).to_string(), (a + b).to_string());
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// A cache for the results of a recursive function, counting how often a
+/// result could be reused. Its `Debug` output shows these counts instead of
+/// the cached values.
+///
+/// The key is whatever identifies a call, usually a subset of the arguments:
+/// a function of `(memo, springs, groups)` that only ever shortens both slices
+/// can use `(springs.len(), groups.len())`.
+#[derive(Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: Stats,
+}
+
+/// How many lookups of a `Memo` found a cached result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    /// The share of lookups that were hits, 0 if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo {
+            cache: HashMap::new(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+
+    /// The cached result for `key`, or the one `compute` returns, which is
+    /// cached. `compute` gets the memo back to make recursive calls with.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Calls `f` with `args` through the cache, for recursive closures. `f`
+    /// recurses through its first argument and `key` picks what identifies a
+    /// call, e.g. `memo.recurse(n, |&n| n, &|fib, n| fib(n - 1) + fib(n - 2))`
+    /// with a base case.
+    pub fn recurse<A>(
+        &mut self,
+        args: A,
+        key: impl Fn(&A) -> K + Copy,
+        f: &impl Fn(&mut dyn FnMut(A) -> V, A) -> V,
+    ) -> V {
+        self.get_or_insert_with(key(&args), |memo| {
+            f(&mut |args| memo.recurse(args, key, f), args)
+        })
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Forgets all results and resets the statistics, e.g. between the two
+    /// parts of a puzzle when they compute different things for the same keys.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = Stats::default();
+    }
+}
+
+impl<K, V> fmt::Debug for Memo<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Memo")
+            .field("entries", &self.cache.len())
+            .field("hits", &self.stats.hits)
+            .field("misses", &self.stats.misses)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    /// The number of ways to split `text` into `words`, keyed only by what is left of `text`.
+    fn splits(memo: &mut Memo<usize, u64>, text: &str, words: &[&str]) -> u64 {
+        if text.is_empty() {
+            return 1;
+        }
+        memo.get_or_insert_with(text.len(), |memo| {
+            words
+                .iter()
+                .filter_map(|word| text.strip_prefix(word))
+                .map(|rest| splits(memo, rest, words))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 87,
+                misses: 89
+            }
+        );
+        assert_eq!(
+            format!("{:?}", memo),
+            "Memo { entries: 89, hits: 87, misses: 89 }"
+        );
+
+        let mut memo = Memo::new();
+        assert_eq!(splits(&mut memo, "abababab", &["a", "b", "ab"]), 16);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), Stats::default());
+        assert_eq!(splits(&mut memo, "abc", &["a", "bc"]), 1);
+    }
+
+    #[test]
+    fn test_recurse() {
+        let mut memo = Memo::new();
+        let paths = memo.recurse(
+            (0_usize, 0_usize, "unused"),
+            |&(row, col, _)| (row, col),
+            &|paths, (row, col, label)| {
+                if row == 16 || col == 16 {
+                    1_u64
+                } else {
+                    paths((row + 1, col, label)) + paths((row, col + 1, label))
+                }
+            },
+        );
+        // lattice paths through a 16x16 grid: 32 choose 16.
+        assert_eq!(paths, 601_080_390);
+        assert_eq!(memo.get(&(15, 15)), Some(&2));
+        assert!(memo.stats().hit_rate() > 0.4);
+        assert_eq!(
+            Stats { hits: 1, misses: 3 }.to_string(),
+            "1 hits, 3 misses (25.0% hit rate)"
+        );
+    }
+}
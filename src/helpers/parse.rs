@@ -0,0 +1,418 @@
+use std::fmt;
+use std::str::FromStr;
+
+pub use advent_of_code_derive::{ParsePattern, ParseToken};
+
+/// Where and why parsing failed. `line` and `column` count from 1 and refer to
+/// the text given to the function that failed; `lines` and `blocks` shift
+/// `line` so that it refers to their whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error on the first line, for use in parsers passed to `lines` and `blocks`.
+    pub fn new(column: usize, text: &str, message: impl fmt::Display) -> ParseError {
+        ParseError {
+            line: 1,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn below(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} in '{}'",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> String {
+        error.to_string()
+    }
+}
+
+/// Parses all of `text` with `FromStr`.
+pub fn value<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|e| ParseError::new(1, text, e))
+}
+
+/// Every integer in `line`, ignoring whatever is between them. A `-` directly
+/// before the digits makes an integer negative unless it follows a letter or
+/// digit, so both `x=-3` and `2-4` give the expected numbers.
+pub fn integers<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    integer_tokens(line)
+        .map(|(column, token)| token.parse().map_err(|e| ParseError::new(column, token, e)))
+        .collect()
+}
+
+/// Exactly as many integers as `T` has fields, e.g. the three of `move 1 from 2 to 3`.
+pub fn integer_tuple<T: Tuple>(line: &str) -> Result<T, ParseError> {
+    T::from_parts(line, &integer_tokens(line).collect::<Vec<_>>())
+}
+
+/// Splits `line` at any of `separators` into as many parts as `T` has fields,
+/// each parsed with its own `FromStr`, e.g. `(u32, u32, u32, u32)` from `2-4,6-8`.
+pub fn split<T: Tuple>(line: &str, separators: &[char]) -> Result<T, ParseError> {
+    let mut parts = vec![];
+    let mut start = 0;
+    for (offset, c) in line.char_indices() {
+        if separators.contains(&c) {
+            parts.push((column_at(line, start), &line[start..offset]));
+            start = offset + c.len_utf8();
+        }
+    }
+    parts.push((column_at(line, start), &line[start..]));
+    T::from_parts(line, &parts)
+}
+
+/// The text between `literals`, with columns, for lines that start with the
+/// first literal, end with the last one and contain the others in order.
+/// This is the matching behind `#[derive(ParsePattern)]`; each capture ends at
+/// the first occurrence of the literal that follows it.
+pub fn match_pattern<'a>(
+    line: &'a str,
+    literals: &[&str],
+) -> Result<Vec<(usize, &'a str)>, ParseError> {
+    let expected = |offset: usize, literal: &str| {
+        ParseError::new(
+            column_at(line, offset),
+            &line[offset..],
+            format!("expected '{}'", literal),
+        )
+    };
+    let Some((first, rest)) = literals.split_first() else {
+        return Ok(vec![]);
+    };
+    if !line.starts_with(first) {
+        return Err(expected(0, first));
+    }
+
+    let mut captures = vec![];
+    let mut start = first.len();
+    for (index, literal) in rest.iter().enumerate() {
+        let end = if index + 1 == rest.len() {
+            line.len()
+                .checked_sub(literal.len())
+                .filter(|&end| end >= start && line.ends_with(literal))
+                .ok_or_else(|| expected(start, literal))?
+        } else {
+            start
+                + line[start..]
+                    .find(literal)
+                    .ok_or_else(|| expected(start, literal))?
+        };
+        captures.push((column_at(line, start), &line[start..end]));
+        start = end + literal.len();
+    }
+    Ok(captures)
+}
+
+/// Parses a capture of `match_pattern` into the field called `name`.
+pub fn capture<T>((column, text): (usize, &str), name: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse()
+        .map_err(|e| ParseError::new(column, text, format!("invalid `{}`: {}", name, e)))
+}
+
+/// Parses every line of `input`, failing on the first error.
+pub fn lines<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|e| e.below(index)))
+        .collect()
+}
+
+/// Parses the blocks of `input` that are separated by one or more blank lines,
+/// e.g. `blocks(input, |block| lines(block, value::<u32>))`.
+pub fn blocks<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let mut blocks = vec![];
+    let mut block: Vec<&str> = vec![];
+    let mut first_line = 0;
+    for (index, line) in input.lines().chain([""]).enumerate() {
+        if !line.trim().is_empty() {
+            if block.is_empty() {
+                first_line = index;
+            }
+            block.push(line);
+        } else if !block.is_empty() {
+            blocks.push(parse(&block.join("\n")).map_err(|e| e.below(first_line))?);
+            block.clear();
+        }
+    }
+    Ok(blocks)
+}
+
+/// Tuples that `split` and `integer_tuple` fill from the parts of a line.
+pub trait Tuple: Sized {
+    const LEN: usize;
+
+    /// Parses `parts`, given with their columns, which were taken from `line`.
+    fn from_parts(line: &str, parts: &[(usize, &str)]) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_tuple {
+    ($len:literal: $($name:ident $index:tt),+) => {
+        impl<$($name),+> Tuple for ($($name,)+)
+        where
+            $($name: FromStr, $name::Err: fmt::Display),+
+        {
+            const LEN: usize = $len;
+
+            fn from_parts(line: &str, parts: &[(usize, &str)]) -> Result<Self, ParseError> {
+                check_len(line, parts, Self::LEN)?;
+                Ok(($(parse_part::<$name>(parts[$index])?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(2: A 0, B 1);
+impl_tuple!(3: A 0, B 1, C 2);
+impl_tuple!(4: A 0, B 1, C 2, D 3);
+impl_tuple!(5: A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+fn check_len(line: &str, parts: &[(usize, &str)], len: usize) -> Result<(), ParseError> {
+    let message = || format!("expected {} parts, found {}", len, parts.len());
+    match parts.get(len) {
+        // point at the first superfluous part, or past the end if it is an empty trailing one.
+        Some(&(column, _)) => match line.char_indices().nth(column - 1) {
+            Some((offset, _)) => Err(ParseError::new(column, &line[offset..], message())),
+            None => Err(ParseError::new(line.chars().count() + 1, "", message())),
+        },
+        None if parts.len() < len => {
+            Err(ParseError::new(line.chars().count() + 1, line, message()))
+        }
+        None => Ok(()),
+    }
+}
+
+fn parse_part<T>((column, text): (usize, &str)) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|e| ParseError::new(column, text, e))
+}
+
+fn column_at(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+/// The integers of `line` as text, with their columns.
+fn integer_tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let chars = line.char_indices().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let is_sign = c == '-'
+            && chars
+                .get(i + 1)
+                .is_some_and(|(_, next)| next.is_ascii_digit())
+            && (i == 0 || !chars[i - 1].1.is_alphanumeric());
+        if !c.is_ascii_digit() && !is_sign {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while chars.get(i).is_some_and(|(_, c)| c.is_ascii_digit()) {
+            i += 1;
+        }
+        let end = chars.get(i).map_or(line.len(), |&(offset, _)| offset);
+        tokens.push((start + 1, &line[offset..end]));
+    }
+    tokens.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            integers::<i64>("Sensor at x=-2, y=15: beacon at x=10, y=-16"),
+            Ok(vec![-2, 15, 10, -16])
+        );
+        assert_eq!(integers::<u32>("2-4,6-8"), Ok(vec![2, 4, 6, 8]));
+        assert_eq!(integers::<i32>("a1 - -1"), Ok(vec![1, -1]));
+        assert_eq!(integers::<u8>("no numbers"), Ok(vec![]));
+
+        let error = integers::<u8>("x 12 300").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 6, "300")
+        );
+        let error = integers::<u8>("-5").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (1, "-5"));
+    }
+
+    #[test]
+    fn test_integer_tuple() {
+        assert_eq!(
+            integer_tuple::<(u32, usize, usize)>("move 1 from 2 to 3"),
+            Ok((1, 2, 3))
+        );
+
+        let error = integer_tuple::<(u32, u32)>("1 2 3 4").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (5, "3 4"));
+        assert_eq!(error.message, "expected 2 parts, found 4");
+        let error = integer_tuple::<(u32, u32, u32)>("1 2").unwrap_err();
+        assert_eq!(error.column, 4);
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split::<(u32, u32, u32, u32)>("2-4,6-8", &['-', ',']),
+            Ok((2, 4, 6, 8))
+        );
+        assert_eq!(
+            split::<(String, char)>("é:x", &[':']),
+            Ok((String::from("é"), 'x'))
+        );
+
+        let error = split::<(u32, u32)>("é-x", &['-']).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (1, "é"));
+        let error = split::<(u32, u32)>("1é-x", &['-']).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (1, "1é"));
+        let error = split::<(u32, u32)>("1-x", &['-']).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (3, "x"));
+        let error = split::<(u32, u32)>("1-2-3", &['-']).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (5, "3"));
+        let error = split::<(u32, u32)>("1-2-", &['-']).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (5, ""));
+        assert_eq!(error.message, "expected 2 parts, found 3");
+    }
+
+    #[derive(Debug, PartialEq, ParsePattern)]
+    #[pattern("{name} at x={x}, y={y}: {{{type}}}")]
+    struct Sensor {
+        name: String,
+        x: i32,
+        y: i32,
+        r#type: char,
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            "S1 at x=-2, y=15: {b}".parse(),
+            Ok(Sensor {
+                name: String::from("S1"),
+                x: -2,
+                y: 15,
+                r#type: 'b',
+            })
+        );
+
+        let error = "S1 at x=a, y=15: {b}".parse::<Sensor>().unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (9, "a"));
+        assert!(error.message.starts_with("invalid `x`: "), "{}", error);
+        let error = "S1 at x=1 y=15: {b}".parse::<Sensor>().unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (9, "expected ', y='")
+        );
+        let error = "S1 at x=1, y=15: {b".parse::<Sensor>().unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (19, "expected '}'"));
+    }
+
+    #[derive(Debug, PartialEq, ParseToken)]
+    enum Tile {
+        #[token("#")]
+        Wall,
+        #[token(".", "O")]
+        Floor,
+        #[token("<>")]
+        Door,
+    }
+
+    #[test]
+    fn test_parse_token() {
+        assert_eq!("<>".parse(), Ok(Tile::Door));
+        assert_eq!(Tile::try_from('O'), Ok(Tile::Floor));
+        assert_eq!(Tile::Floor.to_string(), ".");
+
+        let error = Tile::try_from('x').unwrap_err();
+        assert_eq!(error.text, "x");
+        assert_eq!(error.message, "expected '#', '.', 'O' or '<>'");
+    }
+
+    #[test]
+    fn test_match_pattern() {
+        assert_eq!(
+            match_pattern("a-b-c", &["", "-", ""]),
+            Ok(vec![(1, "a"), (3, "b-c")])
+        );
+        assert_eq!(match_pattern("é!", &["é", "!"]), Ok(vec![(2, "")]));
+        assert_eq!(
+            match_pattern("turn on", &["turn off"]).unwrap_err().message,
+            "expected 'turn off'"
+        );
+        // the suffix may not overlap the prefix.
+        assert!(match_pattern("ab", &["ab", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let input = "1\n2\n\n\n3\n4\n5\n";
+        assert_eq!(
+            blocks(input, |block| lines(block, value::<u32>)),
+            Ok(vec![vec![1, 2], vec![3, 4, 5]])
+        );
+
+        let error = blocks("1\n\n2\n3x\n", |block| lines(block, value::<u32>)).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (4, 1, "3x")
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: invalid digit found in string in '3x'"
+        );
+
+        let error = lines("1 2\n3 x 4", integers::<u32>)
+            .and_then(|_| lines("1 2\n3 4 5", integer_tuple::<(u32, u32)>))
+            .unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 5, "5"));
+    }
+}
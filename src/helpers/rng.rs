@@ -0,0 +1,52 @@
+/// A small xorshift generator, so input generators and randomized tests
+/// are reproducible without pulling in a dependency.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `range`, slightly biased for very large ranges.
+    ///
+    /// Panics if `range` is empty, and so does `pick` for an empty slice.
+    pub fn range(&mut self, range: std::ops::Range<usize>) -> usize {
+        assert!(
+            !range.is_empty(),
+            "cannot pick from the empty range {:?}",
+            range
+        );
+        range.start + (self.next_u64() % (range.end - range.start) as u64) as usize
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(42);
+        let values = (0..100).map(|_| rng.range(3..7)).collect::<Vec<_>>();
+        assert!(values.iter().all(|value| (3..7).contains(value)));
+        assert_eq!(Rng::new(42).next_u64(), Rng::new(42).next_u64());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot pick from the empty range 3..3")]
+    fn test_empty_range() {
+        Rng::new(42).range(3..3);
+    }
+}
@@ -0,0 +1,392 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path to a goal together with its cost. `path` starts at one of the
+/// start nodes and ends at the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<N, C> {
+    pub cost: C,
+    pub path: Vec<N>,
+}
+
+/// Nodes seen so far, each with the node it was reached from.
+struct Visited<N> {
+    indices: HashMap<N, usize>,
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<N: Hash + Eq + Clone> Visited<N> {
+    fn new() -> Visited<N> {
+        Visited {
+            indices: HashMap::new(),
+            nodes: vec![],
+            parents: vec![],
+        }
+    }
+
+    /// The index of `node`, and whether it was seen for the first time.
+    fn insert(&mut self, node: N, parent: Option<usize>) -> (usize, bool) {
+        if let Some(&idx) = self.indices.get(&node) {
+            return (idx, false);
+        }
+        let idx = self.nodes.len();
+        self.indices.insert(node.clone(), idx);
+        self.nodes.push(node);
+        self.parents.push(parent);
+        (idx, true)
+    }
+
+    fn path(&self, idx: usize) -> Vec<N> {
+        let mut path = std::iter::successors(Some(idx), |&idx| self.parents[idx])
+            .map(|idx| self.nodes[idx].clone())
+            .collect::<Vec<_>>();
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search from all `starts` at once. Nodes can be any `Hash + Eq`
+/// state, `neighbours` produces the edges of a node on demand. Returns the
+/// shortest path, counted in edges, to the first node that `is_goal` accepts.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let (idx, true) = visited.insert(start, None) {
+            queue.push_back((idx, 0));
+        }
+    }
+
+    while let Some((idx, distance)) = queue.pop_front() {
+        if is_goal(&visited.nodes[idx]) {
+            return Some(Found {
+                cost: distance,
+                path: visited.path(idx),
+            });
+        }
+        for next in neighbours(&visited.nodes[idx]) {
+            if let (next, true) = visited.insert(next, Some(idx)) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    None
+}
+
+/// The distance, counted in edges, from the nearest of `starts` to every reachable node.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Depth-first search from `starts`. Returns *a* path to the first goal it
+/// reaches, which is not necessarily the shortest one. `cost` is its length in edges.
+pub fn dfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut stack = vec![];
+    for start in starts {
+        if let (idx, true) = visited.insert(start, None) {
+            stack.push(idx);
+        }
+    }
+    // visit starts in the given order.
+    stack.reverse();
+
+    while let Some(idx) = stack.pop() {
+        if is_goal(&visited.nodes[idx]) {
+            let path = visited.path(idx);
+            return Some(Found {
+                cost: path.len() - 1,
+                path,
+            });
+        }
+        for next in neighbours(&visited.nodes[idx]) {
+            if let (next, true) = visited.insert(next, Some(idx)) {
+                stack.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm from all `starts` at once, `neighbours` yields each
+/// neighbour with the cost of the edge to it. Costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from all `starts` at once. `heuristic` estimates the remaining
+/// cost to a goal and must never overestimate it, or the path may not be the cheapest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited::new();
+    let mut costs = vec![];
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let (idx, true) = visited.insert(start, None) {
+            costs.push(C::default());
+            queue.push(Reverse((heuristic(&visited.nodes[idx]), idx)));
+        }
+    }
+
+    while let Some(Reverse((estimate, idx))) = queue.pop() {
+        let cost = costs[idx];
+        // a cheaper way to this node was queued after this entry.
+        if estimate > cost + heuristic(&visited.nodes[idx]) {
+            continue;
+        }
+        if is_goal(&visited.nodes[idx]) {
+            return Some(Found {
+                cost,
+                path: visited.path(idx),
+            });
+        }
+        for (next, edge) in neighbours(&visited.nodes[idx]) {
+            let next_cost = cost + edge;
+            let (next, is_new) = visited.insert(next, Some(idx));
+            if is_new {
+                costs.push(next_cost);
+            } else if next_cost < costs[next] {
+                costs[next] = next_cost;
+                visited.parents[next] = Some(idx);
+            } else {
+                continue;
+            }
+            queue.push(Reverse((next_cost + heuristic(&visited.nodes[next]), next)));
+        }
+    }
+    None
+}
+
+/// Orders `nodes` and every node reachable from them so each node comes
+/// before its successors, keeping the given order where possible.
+/// Fails with the nodes that are part of or behind a cycle.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    for node in nodes {
+        visited.insert(node, None);
+    }
+
+    let mut edges: Vec<Vec<usize>> = vec![];
+    let mut in_degrees = vec![0; visited.nodes.len()];
+    let mut idx = 0;
+    while idx < visited.nodes.len() {
+        let targets = successors(&visited.nodes[idx])
+            .into_iter()
+            .map(|next| visited.insert(next, None).0)
+            .collect::<Vec<_>>();
+        in_degrees.resize(visited.nodes.len(), 0);
+        for &target in &targets {
+            in_degrees[target] += 1;
+        }
+        edges.push(targets);
+        idx += 1;
+    }
+
+    let mut queue = (0..visited.nodes.len())
+        .filter(|&idx| in_degrees[idx] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(visited.nodes.len());
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        for &next in &edges[idx] {
+            in_degrees[next] -= 1;
+            if in_degrees[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if order.len() == visited.nodes.len() {
+        Ok(order
+            .into_iter()
+            .map(|idx| visited.nodes[idx].clone())
+            .collect())
+    } else {
+        Err((0..visited.nodes.len())
+            .filter(|&idx| in_degrees[idx] > 0)
+            .map(|idx| visited.nodes[idx].clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::Grid;
+
+    /// 0 - 1 - 2 - 3, with a shortcut 0 - 4 - 3 and an unreachable 5.
+    fn neighbours(node: &u8) -> Vec<u8> {
+        match node {
+            0 => vec![1, 4],
+            1 => vec![0, 2],
+            2 => vec![1, 3],
+            3 => vec![2, 4],
+            4 => vec![0, 3],
+            _ => vec![],
+        }
+    }
+
+    /// The shortcut is cheaper in edges but more expensive in cost.
+    fn weighted(node: &u8) -> Vec<(u8, u32)> {
+        neighbours(node)
+            .into_iter()
+            .map(|next| (next, if next == 4 || *node == 4 { 5 } else { 1 }))
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let found = bfs([0], neighbours, |&n| n == 3).unwrap();
+        assert_eq!(
+            found,
+            Found {
+                cost: 2,
+                path: vec![0, 4, 3]
+            }
+        );
+
+        let found = bfs([0, 2], neighbours, |&n| n == 3).unwrap();
+        assert_eq!(found.path, [2, 3]);
+        assert_eq!(bfs([0], neighbours, |&n| n == 5), None);
+
+        let distances = bfs_distances([0], neighbours);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&2], 2);
+    }
+
+    #[test]
+    fn test_dfs() {
+        let found = dfs([0], neighbours, |&n| n == 3).unwrap();
+        assert_eq!(found.path.first(), Some(&0));
+        assert_eq!(found.path.last(), Some(&3));
+        assert_eq!(found.cost, found.path.len() - 1);
+        assert!(found
+            .path
+            .windows(2)
+            .all(|w| neighbours(&w[0]).contains(&w[1])));
+        assert_eq!(dfs([5], neighbours, |&n| n == 0), None);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let found = dijkstra([0], weighted, |&n| n == 3).unwrap();
+        assert_eq!(
+            found,
+            Found {
+                cost: 3,
+                path: vec![0, 1, 2, 3]
+            }
+        );
+
+        let found = dijkstra([0, 3], weighted, |&n| n == 4).unwrap();
+        assert_eq!(found.cost, 5);
+
+        // shortest path through an open grid, with the Manhattan distance as heuristic.
+        let grid = Grid::parse("...#\n.#..\n...#\n#...", |c| c == '#').unwrap();
+        let goal: (usize, usize) = (3, 3);
+        let found = astar(
+            [(0, 0)],
+            |&position| {
+                grid.neighbours_4(position)
+                    .filter(|&next| !grid[next])
+                    .map(|next| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |&(row, col): &(usize, usize)| goal.0.abs_diff(row) + goal.1.abs_diff(col),
+            |&position| position == goal,
+        )
+        .unwrap();
+        assert_eq!(found.cost, 6);
+        assert_eq!(found.path.len(), 7);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let successors = |node: &char| match node {
+            'a' => vec!['b', 'c'],
+            'b' => vec!['d'],
+            'c' => vec!['d'],
+            _ => vec![],
+        };
+        assert_eq!(
+            topological_sort(['a'], successors),
+            Ok(vec!['a', 'b', 'c', 'd'])
+        );
+
+        let cyclic = |node: &char| match node {
+            'a' => vec!['b'],
+            'b' => vec!['c'],
+            'c' => vec!['b'],
+            _ => vec![],
+        };
+        assert_eq!(topological_sort(['a'], cyclic), Err(vec!['b', 'c']));
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::grid::Grid;
+use super::point::Point2;
+
+/// Cells of an unbounded 2D plane, only occupied points are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Parses a character grid, `cell` returns `None` for empty cells.
+    /// The first character of the first line is at the origin.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> SparseGrid<T> {
+        let cells = input
+            .lines()
+            .zip(0..)
+            .flat_map(|(line, y)| {
+                line.chars()
+                    .zip(0..)
+                    .map(move |(c, x)| (Point2::new(x, y), c))
+            })
+            .filter_map(|(point, c)| Some((point, cell(c)?)))
+            .collect();
+        SparseGrid { cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: Point2, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point2) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// Occupied points with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// The smallest and largest coordinates of the occupied points.
+    pub fn bounds(&self) -> Option<(Point2, Point2)> {
+        self.cells.keys().fold(None, |bounds, &point| match bounds {
+            None => Some((point, point)),
+            Some((min, max)) => Some((
+                Point2::new(min.x.min(point.x), min.y.min(point.y)),
+                Point2::new(max.x.max(point.x), max.y.max(point.y)),
+            )),
+        })
+    }
+
+    /// A dense grid covering `bounds`, its top left cell is the minimum.
+    pub fn to_grid<U>(&self, mut cell: impl FnMut(Option<&T>) -> U) -> Grid<U> {
+        let Some((min, max)) = self.bounds() else {
+            return Grid::from_rows(vec![]).unwrap();
+        };
+        let rows = (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| cell(self.get(Point2::new(x, y))))
+                    .collect()
+            })
+            .collect();
+        Grid::from_rows(rows).unwrap()
+    }
+}
+
+impl<T: Clone> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> SparseGrid<T> {
+        SparseGrid {
+            cells: grid
+                .iter()
+                .map(|(position, value)| (Point2::from_position(position), value.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Renders the occupied area, empty cells are shown as `.`.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let grid = self.to_grid(|value| match value {
+            Some(value) => value.to_string(),
+            None => String::from("."),
+        });
+        write!(f, "{}", grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut sparse = SparseGrid::parse("#..\n..#", |c| (c == '#').then_some('#'));
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Point2::new(2, 1)));
+
+        sparse.insert(Point2::new(-1, -1), '@');
+        assert_eq!(
+            sparse.bounds(),
+            Some((Point2::new(-1, -1), Point2::new(2, 1)))
+        );
+        assert_eq!(sparse.to_string(), "@...\n.#..\n...#");
+
+        let grid = Grid::parse("ab\ncd", |c| c).unwrap();
+        let sparse = SparseGrid::from(&grid);
+        assert_eq!(sparse.get(Point2::new(1, 0)), Some(&'b'));
+        assert_eq!(sparse.to_grid(|c| *c.unwrap()), grid);
+    }
+}
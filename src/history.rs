@@ -0,0 +1,411 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::environment;
+use crate::render::Mode;
+use crate::runner::PartResult;
+
+const HEADER: &str = "# timestamp\tcommit\tdirty\trustc\tcpu\tday\tpart\tnanos";
+
+/// Where and when a benchmark run happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunInfo {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: String,
+    pub dirty: bool,
+    pub rustc: String,
+    pub cpu: String,
+}
+
+/// A single part's timing within a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub run: RunInfo,
+    pub day: u8,
+    pub part: u8,
+    pub time: Duration,
+}
+
+/// All timings of one part, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trend {
+    pub day: u8,
+    pub part: u8,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Keeps free-form values from breaking the tab-separated log.
+fn sanitize(value: &str) -> String {
+    value.replace(['\t', '\n'], " ")
+}
+
+impl RunInfo {
+    /// Describes the current checkout and machine. Values that cannot be
+    /// determined are recorded as `unknown`.
+    pub fn collect() -> RunInfo {
+        let unknown = || String::from("unknown");
+        RunInfo {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            commit: command_output("git", &["rev-parse", "HEAD"]).unwrap_or_else(unknown),
+            dirty: command_output("git", &["status", "--porcelain"])
+                .map(|status| !status.is_empty())
+                .unwrap_or(false),
+            rustc: environment::RUSTC.to_string(),
+            cpu: environment::cpu_model().unwrap_or_else(unknown),
+        }
+    }
+
+    /// The abbreviated commit hash, suffixed with `*` for dirty checkouts.
+    pub fn short_commit(&self) -> String {
+        let commit = self.commit.get(..7).unwrap_or(&self.commit);
+        format!("{}{}", commit, if self.dirty { "*" } else { "" })
+    }
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Entry> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        match fields[..] {
+            [timestamp, commit, dirty, rustc, cpu, day, part, nanos] => Some(Entry {
+                run: RunInfo {
+                    timestamp: timestamp.parse().ok()?,
+                    commit: commit.to_string(),
+                    dirty: dirty.parse().ok()?,
+                    rustc: rustc.to_string(),
+                    cpu: cpu.to_string(),
+                },
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                time: Duration::from_nanos(nanos.parse().ok()?),
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.run.timestamp,
+            sanitize(&self.run.commit),
+            self.run.dirty,
+            sanitize(&self.run.rustc),
+            sanitize(&self.run.cpu),
+            self.day,
+            self.part,
+            self.time.as_nanos()
+        )
+    }
+}
+
+impl Trend {
+    pub fn fastest(&self) -> &Entry {
+        self.entries.iter().min_by_key(|entry| entry.time).unwrap()
+    }
+
+    pub fn latest(&self) -> &Entry {
+        self.entries.last().unwrap()
+    }
+
+    /// Relative change from the first to the latest run in percent, positive when slower.
+    pub fn change(&self) -> f64 {
+        let first = self.entries[0].time.as_secs_f64();
+        if first == 0_f64 {
+            return 0_f64;
+        }
+        (self.latest().time.as_secs_f64() / first - 1_f64) * 100_f64
+    }
+}
+
+impl History {
+    pub fn parse(contents: &str) -> Result<History, String> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(idx, line)| {
+                Entry::parse(line).ok_or(format!("invalid history entry on line {}", idx + 1))
+            })
+            .collect::<Result<_, _>>()
+            .map(|entries| History { entries })
+    }
+
+    pub fn load(path: &Path) -> io::Result<History> {
+        History::parse(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Appends every solved part of `results` to the log at `path`, creating it if needed.
+    pub fn append(path: &Path, run: &RunInfo, results: &[PartResult]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+
+        let mut lines = String::new();
+        if is_new {
+            writeln!(lines, "{}", HEADER).unwrap();
+        }
+        for result in results.iter().filter(|result| result.is_solved()) {
+            let entry = Entry {
+                run: run.clone(),
+                day: result.day,
+                part: result.part,
+                time: result.time(),
+            };
+            writeln!(lines, "{}", entry.to_line()).unwrap();
+        }
+        file.write_all(lines.as_bytes())
+    }
+
+    /// Groups the log by day and part, ordered by day.
+    pub fn trends(&self) -> Vec<Trend> {
+        let mut grouped: BTreeMap<(u8, u8), Vec<Entry>> = BTreeMap::new();
+        for entry in &self.entries {
+            grouped
+                .entry((entry.day, entry.part))
+                .or_default()
+                .push(entry.clone());
+        }
+        grouped
+            .into_iter()
+            .map(|((day, part), mut entries)| {
+                entries.sort_by_key(|entry| entry.run.timestamp);
+                Trend { day, part, entries }
+            })
+            .collect()
+    }
+}
+
+/// Renders the trends as a plain text table.
+pub fn render_text(trends: &[Trend], mode: Mode) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<4} {:<5} {:>5}  {:<22} {:<22} {:>8}",
+        "Day", "Part", "Runs", "Fastest", "Latest", "Change"
+    )
+    .unwrap();
+    for trend in trends {
+        let (fastest, latest) = (trend.fastest(), trend.latest());
+        writeln!(
+            out,
+            "{:<4} {:<5} {:>5}  {:<22} {:<22} {:>+7.1}%",
+            format!("{:02}", trend.day),
+            trend.part,
+            trend.entries.len(),
+            format!(
+                "{} ({})",
+                mode.duration(fastest.time),
+                fastest.run.short_commit()
+            ),
+            format!(
+                "{} ({})",
+                mode.duration(latest.time),
+                latest.run.short_commit()
+            ),
+            trend.change()
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// An SVG polyline of the timings, scaled to fit `width` × `height`.
+fn sparkline(trend: &Trend, width: f64, height: f64) -> String {
+    let times = trend
+        .entries
+        .iter()
+        .map(|entry| entry.time.as_secs_f64())
+        .collect::<Vec<_>>();
+    let min = times.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = times.iter().cloned().fold(0_f64, f64::max);
+    let step = if times.len() > 1 {
+        width / (times.len() - 1) as f64
+    } else {
+        0_f64
+    };
+
+    let points = times
+        .iter()
+        .enumerate()
+        .map(|(idx, time)| {
+            let y = if max > min {
+                height - (time - min) / (max - min) * height
+            } else {
+                height / 2_f64
+            };
+            format!("{:.1},{:.1}", idx as f64 * step, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        r#"<svg width="{w}" height="{h}" viewBox="-2 -2 {vw} {vh}"><polyline fill="none" stroke="currentColor" stroke-width="1.5" points="{points}"/></svg>"#,
+        w = width,
+        h = height,
+        vw = width + 4_f64,
+        vh = height + 4_f64,
+        points = points
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the trends as a self-contained HTML page with a sparkline per part.
+pub fn render_html(trends: &[Trend]) -> String {
+    let mut rows = String::new();
+    for trend in trends {
+        let (fastest, latest) = (trend.fastest(), trend.latest());
+        writeln!(
+            rows,
+            "<tr><td>{:02}</td><td>{}</td><td>{}</td><td>{:.2?} <code title=\"{}\">{}</code></td><td>{:.2?} <code title=\"{}\">{}</code></td><td>{:+.1}%</td><td>{}</td></tr>",
+            trend.day,
+            trend.part,
+            trend.entries.len(),
+            fastest.time,
+            escape_html(&fastest.run.rustc),
+            escape_html(&fastest.run.short_commit()),
+            latest.time,
+            escape_html(&latest.run.rustc),
+            escape_html(&latest.run.short_commit()),
+            trend.change(),
+            sparkline(trend, 120_f64, 24_f64)
+        )
+        .unwrap();
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code timings</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.3em 0.8em; text-align: left; border-bottom: 1px solid #ddd; }}
+svg {{ color: #c0392b; vertical-align: middle; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code timings</h1>
+<table>
+<tr><th>Day</th><th>Part</th><th>Runs</th><th>Fastest</th><th>Latest</th><th>Change</th><th>Trend</th></tr>
+{}</table>
+</body>
+</html>
+"#,
+        rows
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(timestamp: u64, commit: &str) -> RunInfo {
+        RunInfo {
+            timestamp,
+            commit: commit.into(),
+            dirty: false,
+            rustc: "rustc 1.65.0".into(),
+            cpu: "Test CPU".into(),
+        }
+    }
+
+    fn result(day: u8, part: u8, micros: u64) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: Some("0".into()),
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        // unique per process, so concurrent test runs do not share the file.
+        let path = std::env::temp_dir().join(format!(
+            "advent_of_code_test_history_{}.tsv",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        History::append(&path, &run(1, "aaaaaaaaaa"), &[result(1, 1, 100)]).unwrap();
+        History::append(&path, &run(2, "bbbbbbbbbb"), &[result(1, 1, 80)]).unwrap();
+        let history = History::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[1].run, run(2, "bbbbbbbbbb"));
+        assert_eq!(history.entries[1].time, Duration::from_micros(80));
+        assert!(History::parse("1\tabc\tmaybe\trustc\tcpu\t1\t1\t100").is_err());
+    }
+
+    #[test]
+    fn test_trends() {
+        let entry = |timestamp, commit, day, micros| Entry {
+            run: run(timestamp, commit),
+            day,
+            part: 1,
+            time: Duration::from_micros(micros),
+        };
+        let history = History {
+            entries: vec![
+                entry(1, "aaaaaaaaaa", 2, 200),
+                entry(1, "aaaaaaaaaa", 1, 100),
+                entry(2, "bbbbbbbbbb", 1, 50),
+                entry(3, "cccccccccc", 1, 75),
+            ],
+        };
+
+        let trends = history.trends();
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].day, 1);
+        assert_eq!(trends[0].fastest().run.short_commit(), "bbbbbbb");
+        assert_eq!(trends[0].latest().time, Duration::from_micros(75));
+        assert!((trends[0].change() + 25_f64).abs() < 1.0e-6);
+
+        let text = render_text(&trends, Mode::Color);
+        assert!(text.contains("50.00µs (bbbbbbb)"));
+        assert!(render_text(&trends, Mode::Plain).contains("50.00us (bbbbbbb)"));
+        assert!(render_html(&trends).contains("points=\"0.0,0.0 60.0,24.0 120.0,12.0\""));
+    }
+}
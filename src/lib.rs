@@ -5,8 +5,27 @@
  */
 use std::env;
 use std::fs;
-
+use std::path::PathBuf;
+
+// lets the derive macros, which refer to `::advent_of_code`, be used within this crate.
+extern crate self as advent_of_code;
+
+pub mod allocations;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod environment;
+pub mod export;
+pub mod filter;
 pub mod helpers;
+pub mod history;
+pub mod readme;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaling;
+pub mod summary;
+pub mod tuning;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -15,111 +34,70 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+        use advent_of_code::{allocations, render};
         use std::fmt::Display;
         use std::time::Instant;
 
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
+        fn print_result<T: Display>(part: u8, func: impl FnOnce(&str) -> Option<T>, input: &str) {
+            let ((result, elapsed), allocations) = allocations::track(|| {
+                let timer = Instant::now();
+                let result = func(input);
+                (result, timer.elapsed())
+            });
+            let line = match result {
+                Some(result) => render::mode().answer(result, elapsed, allocations.as_ref()),
+                None => String::from("not solved."),
+            };
+            render::print_part(part, &line);
         }
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        print_result($part, $solver, $input);
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
-
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+/// A day's solution with both parts erased to their printed answer.
+/// Days are registered in `src/solutions.rs` via the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub parts: [fn(&str) -> Option<String>; 2],
+    /// Builds an input of roughly `size` units for `scaling`, if the day has one.
+    pub generate: Option<fn(usize) -> String>,
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+#[macro_export]
+macro_rules! solution {
+    ($day:expr, $module:ident) => {
+        $crate::solution!(@build $day, $module, None)
+    };
+    ($day:expr, $module:ident, generate) => {
+        $crate::solution!(@build $day, $module, Some($module::generate))
+    };
+    (@build $day:expr, $module:ident, $generate:expr) => {
+        $crate::Solution {
+            day: $day,
+            parts: [
+                |input| $module::part_one(input).map(|result| result.to_string()),
+                |input| $module::part_two(input).map(|result| result.to_string()),
+            ],
+            generate: $generate,
         }
-    })
+    };
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+pub(crate) fn file_path(folder: &str, day: u8) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join("src").join(folder).join(format!("{:02}.txt", day))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
+pub fn read_file(folder: &str, day: u8) -> String {
+    let f = fs::read_to_string(file_path(folder, day));
+    f.expect("could not open input file")
+}
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
-    }
+/// Like `read_file`, but returns `None` if the file is missing or empty.
+pub fn try_read_file(folder: &str, day: u8) -> Option<String> {
+    fs::read_to_string(file_path(folder, day))
+        .ok()
+        .filter(|contents| !contents.trim().is_empty())
 }
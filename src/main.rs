@@ -2,47 +2,436 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::baseline::{Baseline, Verdict};
+use advent_of_code::environment::Environment;
+use advent_of_code::filter::{self, Filter};
+use advent_of_code::history::{History, RunInfo};
+use advent_of_code::render::{self, Mode};
+use advent_of_code::runner::{self, Options, PartResult};
+use advent_of_code::summary::{self, SortOrder};
+use advent_of_code::tuning::{self, Tuning};
+use advent_of_code::{answers, export, readme, report, scaling, Solution};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+mod solutions;
+
+const DEFAULT_BASELINE_PATH: &str = ".timings/baseline.tsv";
+const DEFAULT_HISTORY_PATH: &str = ".timings/history.tsv";
+
+struct Args {
+    bench: bool,
+    save_baseline: bool,
+    compare: bool,
+    baseline_path: PathBuf,
+    history_path: PathBuf,
+    threshold: f64,
+    fail_on_regression: bool,
+    readme: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    json: bool,
+    filter: Filter,
+    slowest: Option<usize>,
+    sort: SortOrder,
+    budget: Option<Duration>,
+    mode: Option<Mode>,
+    junit: Option<PathBuf>,
+    tap: Option<PathBuf>,
+    save_answers: bool,
+    overwrite_answers: bool,
+    pin_cpu: Option<usize>,
+    high_priority: bool,
+    scale: bool,
+    scale_start: usize,
+    scale_steps: usize,
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let secs = value.parse::<f64>().map_err(|e| e.to_string())?;
+    Duration::try_from_secs_f64(secs).map_err(|e| e.to_string())
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let fail_on_regression = args.contains("--fail-on-regression");
+    let sequential = args.contains("--sequential");
+    Ok(Args {
+        bench: args.contains("--bench"),
+        save_baseline: args.contains("--save-baseline"),
+        compare: args.contains("--compare") || fail_on_regression,
+        baseline_path: args
+            .opt_value_from_str("--baseline-file")?
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINE_PATH)),
+        history_path: args
+            .opt_value_from_str("--history-file")?
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_HISTORY_PATH)),
+        threshold: args.opt_value_from_str("--threshold")?.unwrap_or(5_f64),
+        fail_on_regression,
+        readme: args.contains("--readme"),
+        jobs: match args.opt_value_from_str("--jobs")? {
+            Some(jobs) if !sequential => jobs,
+            _ => 1,
+        },
+        timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+        json: args.contains("--json"),
+        filter: Filter {
+            days: args
+                .opt_value_from_fn("--days", filter::parse_days)?
+                .unwrap_or_default(),
+            part: args.opt_value_from_fn("--part", filter::parse_part)?,
+            only_solved: args.contains("--only-solved"),
+            only_failing: args.contains("--only-failing"),
+        },
+        slowest: args.opt_value_from_str("--slowest")?,
+        sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+        budget: args.opt_value_from_fn("--budget", parse_seconds)?,
+        mode: if args.contains("--plain") {
+            Some(Mode::Plain)
+        } else if args.contains("--no-color") {
+            Some(Mode::NoColor)
+        } else {
+            None
+        },
+        junit: args.opt_value_from_str("--junit")?,
+        tap: args.opt_value_from_str("--tap")?,
+        save_answers: args.contains("--save-answers"),
+        overwrite_answers: args.contains("--overwrite-answers"),
+        pin_cpu: args.opt_value_from_str("--pin-cpu")?,
+        high_priority: args.contains("--high-priority"),
+        scale: args.contains("--scale"),
+        scale_start: args.opt_value_from_str("--scale-start")?.unwrap_or(1000),
+        scale_steps: args
+            .opt_value_from_fn("--scale-steps", scaling::parse_steps)?
+            .unwrap_or(8),
+    })
+}
+
+/// Writes a report, creating its directory if needed.
+fn write_report(path: &Path, contents: String) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Prints a human-readable message, on stderr if stdout is reserved for JSON.
+fn note(json: bool, message: impl Display) {
+    if json {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+/// Fits every selected part with an input generator against complexity classes.
+fn run_scaling(args: &Args, solutions: &[Solution]) {
+    let sizes = scaling::sizes(args.scale_start, 2, args.scale_steps);
+    for solution in solutions {
+        let Some(generate) = solution.generate else {
+            println!("Day {:02}: no input generator.", solution.day);
+            continue;
+        };
+        for (part, &func) in (1..).zip(&solution.parts) {
+            if !args.filter.includes_part(part) {
+                continue;
+            }
+            match scaling::analyze(solution.day, part, func, generate, &sizes, args.timeout) {
+                Ok(analysis) => println!("{}", analysis),
+                Err(e) => println!("Day {:02} Part {}: {}.", solution.day, part, e),
+            }
+        }
+    }
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(mode) = args.mode {
+        render::set_mode(mode);
+    }
+
+    let environment = Environment::collect();
+    if args.readme && environment.is_debug() {
+        eprintln!("Refusing to write debug timings to the readme. Use `cargo bench-readme`.");
+        process::exit(1);
+    }
+    if args.readme && args.filter.is_active() {
+        eprintln!("Refusing to write a run filtered by day or part to the readme, it would drop the others.");
+        process::exit(1);
+    }
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
+    if args.pin_cpu.is_some() && args.jobs > 1 {
+        eprintln!("--pin-cpu would run all jobs on a single core, use it with --sequential.");
+        process::exit(1);
+    }
+
+    let mut tuning = Tuning::default();
+    if let Some(core) = args.pin_cpu {
+        match tuning::pin_to_core(core) {
+            Ok(_) => tuning.core = Some(core),
+            Err(e) => {
+                eprintln!("Failed to pin to core {}: {}", core, e);
+                process::exit(1);
             }
+        }
+    }
+    if args.high_priority {
+        match tuning::raise_priority() {
+            Ok(nice) => tuning.nice = Some(nice),
+            Err(e) => eprintln!("Could not raise priority: {}", e),
+        }
+    }
+    if args.bench {
+        tuning.governor = tuning::governor(tuning.core.unwrap_or(0));
+        if let Some(warning) = tuning.governor_warning() {
+            eprintln!("{}", warning);
+        }
+    }
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+    if args.bench || args.json {
+        note(args.json, render::italic(&environment));
+        note(args.json, render::italic(&tuning));
+    }
 
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
+    if args.bench && args.jobs > 1 {
+        eprintln!(
+            "Benchmarking with --jobs {}: timings will be less accurate than with --sequential.",
+            args.jobs
+        );
+    }
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+    let options = Options {
+        bench: args.bench,
+        timeout: args.timeout,
+        part: args.filter.part,
+    };
+    let selected = solutions::SOLUTIONS
+        .iter()
+        .filter(|solution| args.filter.includes_day(solution.day))
+        .copied()
+        .collect::<Vec<_>>();
+    if args.scale {
+        run_scaling(&args, &selected);
+        return;
+    }
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+    let all_days = runner::run_days(&selected, args.jobs, options, |day| {
+        if let Some(day) = args.filter.apply(day).filter(|_| !args.json) {
+            runner::print_day(&day);
+        }
+    });
+    let days = all_days
+        .iter()
+        .filter_map(|day| args.filter.apply(day))
+        .collect::<Vec<_>>();
+    if runner::abandoned_parts() > 0 {
+        eprintln!(
+            "{} part(s) timed out and kept running in the background, timings taken after them are unreliable.",
+            runner::abandoned_parts()
+        );
+    }
+    // status filters only apply to the printed parts, everything else uses every part that ran.
+    let shown: Vec<PartResult> = days
+        .iter()
+        .flat_map(|day| day.parts.iter().flatten().cloned())
+        .collect();
+    let results = args.filter.selected(&all_days);
+
+    let slowest = args.slowest.map(|n| filter::slowest(&shown, n));
+
+    if args.json {
+        print!(
+            "{}",
+            export::to_json(&days, slowest.as_deref(), &environment, &tuning)
+        );
+    } else {
+        println!("----------");
+        print!(
+            "{}",
+            summary::render_summary(&shown, args.sort, render::mode())
+        );
+        println!(
+            "{} {}",
+            render::bold("Total:"),
+            render::italic(format!(
+                "{:.2}ms",
+                summary::total_time(&shown).as_secs_f64() * 1000_f64
+            ))
+        );
+
+        if let Some(slowest) = &slowest {
+            println!("{}", render::bold("Slowest parts:"));
+            for (rank, result) in slowest.iter().enumerate() {
+                println!(
+                    "{}. Day {:02} Part {}: {}",
+                    rank + 1,
+                    result.day,
+                    result.part,
+                    render::duration(result.time())
+                );
+            }
+        }
+    }
+
+    let mut regressed = false;
+    if args.compare {
+        match Baseline::load(&args.baseline_path) {
+            Ok(baseline) => {
+                note(args.json, "----------");
+                note(
+                    args.json,
+                    format!(
+                        "Compared to \"{}\" (threshold: {}%):",
+                        args.baseline_path.display(),
+                        args.threshold
+                    ),
+                );
+                for comparison in baseline.compare(&results, args.threshold) {
+                    regressed |= matches!(comparison.verdict, Verdict::Regressed | Verdict::Failed);
+                    note(args.json, comparison);
                 }
-            );
+                let mismatched = baseline.mismatched(&results);
+                if mismatched > 0 {
+                    note(
+                        args.json,
+                        format!(
+                            "Skipped {} part(s) whose baseline was taken {} --bench.",
+                            mismatched,
+                            if args.bench { "without" } else { "with" }
+                        ),
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to load baseline: {}", e);
+                process::exit(1);
+            }
+        }
+    }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+    if args.save_baseline {
+        // a filtered run only replaces the timings of the parts it ran.
+        let mut baseline = Baseline::default();
+        if args.filter.is_active() {
+            match Baseline::load(&args.baseline_path) {
+                Ok(existing) => baseline = existing,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    eprintln!("Failed to load baseline: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        baseline.merge(&results);
+        match baseline.save(&args.baseline_path) {
+            Ok(_) => note(
+                args.json,
+                format!("Saved baseline to \"{}\"", args.baseline_path.display()),
+            ),
+            Err(e) => {
+                eprintln!("Failed to save baseline: {}", e);
+                process::exit(1);
             }
-        })
-        .sum();
+        }
+    }
+
+    if args.bench && environment.is_debug() {
+        note(
+            args.json,
+            "Debug timings are not recorded in the history, benchmark with --release.",
+        );
+    } else if args.bench {
+        if let Err(e) = History::append(&args.history_path, &RunInfo::collect(), &results) {
+            eprintln!("Failed to append to history: {}", e);
+            process::exit(1);
+        }
+    }
+
+    if args.readme {
+        match readme::update_readme(Path::new("README.md"), &results) {
+            Ok(true) => note(args.json, "Updated benchmarks in \"README.md\""),
+            Ok(false) => note(args.json, "Benchmarks in \"README.md\" are up to date"),
+            Err(e) => {
+                eprintln!("Failed to update readme: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    if args.save_answers {
+        match answers::save(&results, args.overwrite_answers) {
+            Ok(days) if days.is_empty() => note(args.json, "Stored answers are up to date"),
+            Ok(days) => note(
+                args.json,
+                format!("Saved answers of {} day(s) to \"src/answers\"", days.len()),
+            ),
+            Err(e) => {
+                eprintln!("Failed to save answers: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // reports cover every part that ran, status filters only apply to the output above.
+    let cases = report::cases(&all_days, args.filter.part);
+    let reports = [
+        (
+            &args.junit,
+            report::to_junit as fn(&[report::Case]) -> String,
+        ),
+        (&args.tap, report::to_tap),
+    ];
+    for (path, render_report) in reports {
+        let Some(path) = path else { continue };
+        match write_report(path, render_report(&cases)) {
+            Ok(_) => note(args.json, format!("Wrote report to \"{}\"", path.display())),
+            Err(e) => {
+                eprintln!("Failed to write report: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut over_budget = false;
+    if let Some(budget) = args.budget {
+        let total = summary::total_time(&results);
+        over_budget = total > budget;
+        // a partial total is not the one of all days, say so.
+        let scope = match args.filter.selection() {
+            Some(selection) => format!(" {}", render::italic(format!("(only {})", selection))),
+            None => String::new(),
+        };
+        note(
+            args.json,
+            format!(
+                "{} {} of {}{}",
+                render::bold(if over_budget {
+                    "Over budget:"
+                } else {
+                    "Within budget:"
+                }),
+                render::duration(total),
+                render::duration(budget),
+                scope
+            ),
+        );
+    }
 
-    println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
-    );
+    if (regressed && args.fail_on_regression) || over_budget {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,170 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::runner::PartResult;
+
+pub const TABLE_START: &str = "<!--- benchmarking table --->";
+pub const TABLE_END: &str = "<!--- benchmarking end --->";
+
+/// Rounds `time` to two significant digits, so that the usual noise between
+/// two benchmark runs does not change the table.
+fn round_time(time: Duration) -> Duration {
+    let nanos = time.as_nanos();
+    let digits = nanos.checked_ilog10().unwrap_or(0);
+    let factor = 10_u128.pow(digits.saturating_sub(1));
+    let rounded = (nanos + factor / 2) / factor * factor;
+    Duration::from_nanos(rounded as u64)
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => format!("`{:?}`", round_time(time)),
+        None => String::from("-"),
+    }
+}
+
+/// Renders a markdown table with a row for every day that has at least one solved part.
+pub fn render_table(results: &[PartResult]) -> String {
+    let mut days: BTreeMap<u8, [Option<Duration>; 2]> = BTreeMap::new();
+    for result in results.iter().filter(|result| result.is_solved()) {
+        let times = days.entry(result.day).or_default();
+        times[(result.part - 1) as usize] = Some(result.time());
+    }
+
+    let mut table = String::new();
+    writeln!(table, "## Benchmarks").unwrap();
+    writeln!(table).unwrap();
+    writeln!(table, "| Day | Part 1 | Part 2 | Total |").unwrap();
+    writeln!(table, "| :---: | :---: | :---: | :---: |").unwrap();
+
+    let mut total = Duration::ZERO;
+    for (day, [part_one, part_two]) in &days {
+        let day_total = part_one.unwrap_or_default() + part_two.unwrap_or_default();
+        total += day_total;
+        writeln!(
+            table,
+            "| [Day {}](./src/bin/{:02}.rs) | {} | {} | {} |",
+            day,
+            day,
+            format_time(*part_one),
+            format_time(*part_two),
+            format_time(Some(day_total))
+        )
+        .unwrap();
+    }
+
+    writeln!(table).unwrap();
+    writeln!(table, "**Total: {:?}**", round_time(total)).unwrap();
+    table
+}
+
+/// Replaces everything between the table markers in `contents` with `table`.
+/// The rest of `contents` is returned unchanged.
+pub fn replace_table(contents: &str, table: &str) -> Result<String, String> {
+    let start = contents
+        .find(TABLE_START)
+        .ok_or(format!("missing marker {}", TABLE_START))?
+        + TABLE_START.len();
+    let end = contents[start..]
+        .find(TABLE_END)
+        .ok_or(format!("missing marker {}", TABLE_END))?
+        + start;
+
+    Ok(format!(
+        "{}\n{}\n{}",
+        &contents[..start],
+        table.trim_end(),
+        &contents[end..]
+    ))
+}
+
+/// Writes the timings table into the readme at `path`.
+/// Returns whether the file changed; it is left untouched if the table is identical.
+pub fn update_readme(path: &Path, results: &[PartResult]) -> io::Result<bool> {
+    let contents = fs::read_to_string(path)?;
+    let updated = replace_table(&contents, &render_table(results))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if updated == contents {
+        return Ok(false);
+    }
+    fs::write(path, updated)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(day: u8, part: u8, micros: Option<u64>) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: micros.map(|_| "0".into()),
+            elapsed: Duration::from_micros(micros.unwrap_or(0)),
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_render_table() {
+        let table = render_table(&[
+            result(1, 1, Some(100)),
+            result(1, 2, Some(1500)),
+            result(2, 1, Some(20)),
+            result(2, 2, None),
+        ]);
+
+        assert!(table.contains("| [Day 1](./src/bin/01.rs) | `100µs` | `1.5ms` | `1.6ms` |"));
+        assert!(table.contains("| [Day 2](./src/bin/02.rs) | `20µs` | - | `20µs` |"));
+        assert!(table.contains("**Total: 1.6ms**"));
+
+        // timings that only differ by noise render the same table.
+        assert_eq!(
+            render_table(&[result(1, 1, Some(1234))]),
+            render_table(&[result(1, 1, Some(1186))])
+        );
+    }
+
+    #[test]
+    fn test_round_time() {
+        assert_eq!(round_time(Duration::from_nanos(7)), Duration::from_nanos(7));
+        assert_eq!(
+            round_time(Duration::from_nanos(1249)),
+            Duration::from_nanos(1200)
+        );
+        assert_eq!(
+            round_time(Duration::from_nanos(1250)),
+            Duration::from_nanos(1300)
+        );
+        assert_eq!(
+            round_time(Duration::from_millis(996)),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(round_time(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_replace_table() {
+        let readme = format!("# Title\n\n{}\n{}\n\nFooter\n", TABLE_START, TABLE_END);
+        let table = render_table(&[result(1, 1, Some(100))]);
+
+        let updated = replace_table(&readme, &table).unwrap();
+        assert!(updated.starts_with(&format!("# Title\n\n{}\n## Benchmarks\n", TABLE_START)));
+        assert!(updated.ends_with(&format!("**Total: 100µs**\n{}\n\nFooter\n", TABLE_END)));
+        assert_eq!(replace_table(&updated, &table).unwrap(), updated);
+
+        assert!(replace_table("# Title\n", &table).is_err());
+    }
+}
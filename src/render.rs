@@ -0,0 +1,206 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use crate::allocations::AllocStats;
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How terminal output is decorated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// ANSI styles and unicode symbols.
+    Color,
+    /// Unicode symbols without ANSI styles.
+    NoColor,
+    /// ASCII only, without ANSI styles.
+    Plain,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+impl Mode {
+    /// Picks a mode from the `AOC_PLAIN` and `NO_COLOR` environment variables
+    /// and whether stdout is a terminal. Empty variables count as unset.
+    pub fn detect(plain: Option<&str>, no_color: Option<&str>, is_terminal: bool) -> Mode {
+        let is_set = |value: Option<&str>| value.is_some_and(|value| !value.is_empty());
+        if is_set(plain) {
+            Mode::Plain
+        } else if is_set(no_color) || !is_terminal {
+            Mode::NoColor
+        } else {
+            Mode::Color
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Mode> {
+        match value {
+            1 => Some(Mode::Color),
+            2 => Some(Mode::NoColor),
+            3 => Some(Mode::Plain),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Mode::Color => 1,
+            Mode::NoColor => 2,
+            Mode::Plain => 3,
+        }
+    }
+
+    fn style(self, code: &str, text: impl Display) -> String {
+        match self {
+            Mode::Color => format!("{}{}{}", code, text, ANSI_RESET),
+            Mode::NoColor | Mode::Plain => text.to_string(),
+        }
+    }
+
+    pub fn bold(self, text: impl Display) -> String {
+        self.style(ANSI_BOLD, text)
+    }
+
+    pub fn italic(self, text: impl Display) -> String {
+        self.style(ANSI_ITALIC, text)
+    }
+
+    /// Returns `ascii` in plain mode and `unicode` otherwise.
+    pub fn symbol(self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        match self {
+            Mode::Plain => ascii,
+            Mode::Color | Mode::NoColor => unicode,
+        }
+    }
+
+    /// Formats a duration like `{:.2?}`, spelling microseconds as `us` in plain mode.
+    pub fn duration(self, duration: Duration) -> String {
+        let formatted = format!("{:.2?}", duration);
+        match self {
+            Mode::Plain => formatted.replace('µ', "u"),
+            Mode::Color | Mode::NoColor => formatted,
+        }
+    }
+
+    pub fn part_header(self, part: u8) -> String {
+        let decoration = self.symbol("🎄", "--");
+        format!(
+            "{} {} {}",
+            decoration,
+            self.bold(format!("Part {}", part)),
+            decoration
+        )
+    }
+
+    pub fn day_header(self, day: u8) -> String {
+        format!(
+            "----------\n{}\n----------",
+            self.bold(format!("| Day {:02} |", day))
+        )
+    }
+
+    /// Formats a solved part, e.g. `42 (elapsed: 1.20ms)`.
+    pub fn answer(
+        self,
+        answer: impl Display,
+        elapsed: Duration,
+        allocations: Option<&AllocStats>,
+    ) -> String {
+        let mut line = format!(
+            "{} {}",
+            answer,
+            self.italic(format!("(elapsed: {})", self.duration(elapsed)))
+        );
+        if let Some(allocations) = allocations {
+            line.push_str(&format!(" {}", self.italic(format!("({})", allocations))));
+        }
+        line
+    }
+}
+
+/// The output mode of this process, detected on first use unless set with `set_mode`.
+pub fn mode() -> Mode {
+    if let Some(mode) = Mode::from_u8(MODE.load(Ordering::Relaxed)) {
+        return mode;
+    }
+    let mode = Mode::detect(
+        env::var("AOC_PLAIN").ok().as_deref(),
+        env::var("NO_COLOR").ok().as_deref(),
+        io::stdout().is_terminal(),
+    );
+    set_mode(mode);
+    mode
+}
+
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode.to_u8(), Ordering::Relaxed);
+}
+
+pub fn bold(text: impl Display) -> String {
+    mode().bold(text)
+}
+
+pub fn italic(text: impl Display) -> String {
+    mode().italic(text)
+}
+
+pub fn symbol(unicode: &'static str, ascii: &'static str) -> &'static str {
+    mode().symbol(unicode, ascii)
+}
+
+pub fn duration(duration: Duration) -> String {
+    mode().duration(duration)
+}
+
+/// Prints a part's header followed by its result line, as `solve!` and `cargo all` do.
+pub fn print_part(part: u8, line: &str) {
+    println!("{}", mode().part_header(part));
+    println!("{}", line);
+}
+
+pub fn print_day_header(day: u8) {
+    println!("{}", mode().day_header(day));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Mode::detect(None, None, true), Mode::Color);
+        assert_eq!(Mode::detect(None, Some(""), true), Mode::Color);
+        assert_eq!(Mode::detect(None, Some("1"), true), Mode::NoColor);
+        assert_eq!(Mode::detect(None, None, false), Mode::NoColor);
+        assert_eq!(Mode::detect(Some("1"), None, true), Mode::Plain);
+    }
+
+    #[test]
+    fn test_answer() {
+        let elapsed = Duration::from_micros(12);
+        assert_eq!(
+            Mode::Color.answer(42, elapsed, None),
+            "42 \x1b[3m(elapsed: 12.00µs)\x1b[0m"
+        );
+        assert_eq!(
+            Mode::NoColor.answer(42, elapsed, None),
+            "42 (elapsed: 12.00µs)"
+        );
+        assert_eq!(
+            Mode::Plain.answer(42, elapsed, None),
+            "42 (elapsed: 12.00us)"
+        );
+    }
+
+    #[test]
+    fn test_headers() {
+        assert_eq!(Mode::Color.part_header(1), "🎄 \x1b[1mPart 1\x1b[0m 🎄");
+        assert_eq!(Mode::Plain.part_header(2), "-- Part 2 --");
+        assert!(Mode::Plain.day_header(3).is_ascii());
+    }
+}
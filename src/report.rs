@@ -0,0 +1,288 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::runner::{DayResult, PartError, PartResult};
+
+/// Outcome of checking a part against its stored answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the stored one.
+    Passed,
+    /// A wrong answer, a panic or a timeout.
+    Failed { kind: &'static str, message: String },
+    /// Nothing to check, e.g. because the day has no input.
+    Skipped(String),
+}
+
+/// A part of a day, reported as one test case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub time: Duration,
+}
+
+impl Case {
+    fn from_result(result: &PartResult) -> Case {
+        let status = match (&result.error, &result.answer, &result.expected) {
+            (Some(error), _, _) => Status::Failed {
+                kind: match error {
+                    PartError::TimedOut(_) => "timeout",
+                    PartError::Panicked(_) => "panic",
+                },
+                message: error.to_string(),
+            },
+            (None, None, _) => Status::Skipped(String::from("not solved")),
+            (None, Some(_), None) => Status::Skipped(String::from("no stored answer")),
+            (None, Some(answer), Some(expected)) if answer != expected => Status::Failed {
+                kind: "wrong_answer",
+                message: format!("expected {}, got {}", expected, answer),
+            },
+            (None, Some(_), Some(_)) => Status::Passed,
+        };
+
+        Case {
+            day: result.day,
+            part: result.part,
+            status,
+            time: result.time(),
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("day {:02} part {}", self.day, self.part)
+    }
+}
+
+/// Turns the runner's results into test cases. Days without input are
+/// reported as skipped for both parts, or only `part` if given.
+pub fn cases(days: &[DayResult], part: Option<u8>) -> Vec<Case> {
+    days.iter()
+        .flat_map(|day| match &day.parts {
+            Some(parts) => parts.iter().map(Case::from_result).collect::<Vec<_>>(),
+            None => (1..=2)
+                .filter(|p| part.is_none_or(|selected| selected == *p))
+                .map(|p| Case {
+                    day: day.day,
+                    part: p,
+                    status: Status::Skipped(String::from("no input")),
+                    time: Duration::ZERO,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn counts<'a>(cases: impl Iterator<Item = &'a Case>) -> (usize, usize, usize, Duration) {
+    cases.fold(
+        (0, 0, 0, Duration::ZERO),
+        |(tests, failures, skipped, time), case| {
+            (
+                tests + 1,
+                failures + usize::from(matches!(case.status, Status::Failed { .. })),
+                skipped + usize::from(matches!(case.status, Status::Skipped(_))),
+                time + case.time,
+            )
+        },
+    )
+}
+
+/// Renders the cases as a JUnit XML report with one test suite per day.
+pub fn to_junit(cases: &[Case]) -> String {
+    let (tests, failures, skipped, time) = counts(cases.iter());
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        out,
+        r#"<testsuites name="advent_of_code" tests="{}" failures="{}" skipped="{}" time="{:.6}">"#,
+        tests,
+        failures,
+        skipped,
+        time.as_secs_f64()
+    )
+    .unwrap();
+
+    for day_cases in cases.chunk_by(|a, b| a.day == b.day) {
+        let (tests, failures, skipped, time) = counts(day_cases.iter());
+        let suite = format!("day{:02}", day_cases[0].day);
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.6}">"#,
+            suite,
+            tests,
+            failures,
+            skipped,
+            time.as_secs_f64()
+        )
+        .unwrap();
+
+        for case in day_cases {
+            let open = format!(
+                r#"    <testcase classname="{}" name="part {}" time="{:.6}""#,
+                suite,
+                case.part,
+                case.time.as_secs_f64()
+            );
+            match &case.status {
+                Status::Passed => writeln!(out, "{}/>", open),
+                Status::Failed { kind, message } => writeln!(
+                    out,
+                    "{}>\n      <failure type=\"{}\" message=\"{}\"/>\n    </testcase>",
+                    open,
+                    kind,
+                    xml_escape(message)
+                ),
+                Status::Skipped(reason) => writeln!(
+                    out,
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>",
+                    open,
+                    xml_escape(reason)
+                ),
+            }
+            .unwrap();
+        }
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Renders the cases as a TAP version 13 stream, with timings and failure
+/// details in YAML blocks.
+pub fn to_tap(cases: &[Case]) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", cases.len());
+    for (idx, case) in cases.iter().enumerate() {
+        let number = idx + 1;
+        let duration_ms = case.time.as_secs_f64() * 1000_f64;
+        match &case.status {
+            Status::Passed => writeln!(
+                out,
+                "ok {} - {}\n  ---\n  duration_ms: {:.3}\n  ...",
+                number,
+                case.name(),
+                duration_ms
+            ),
+            Status::Failed { kind, message } => writeln!(
+                out,
+                "not ok {} - {}\n  ---\n  type: {}\n  message: {}\n  duration_ms: {:.3}\n  ...",
+                number,
+                case.name(),
+                kind,
+                crate::export::json_string(message),
+                duration_ms
+            ),
+            Status::Skipped(reason) => {
+                writeln!(out, "ok {} - {} # SKIP {}", number, case.name(), reason)
+            }
+        }
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(day: u8, part: u8, answer: Option<&str>, expected: Option<&str>) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: answer.map(String::from),
+            elapsed: Duration::from_micros(250),
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: expected.map(String::from),
+        }
+    }
+
+    fn days() -> Vec<DayResult> {
+        let mut panicked = result(2, 1, None, Some("7"));
+        panicked.error = Some(PartError::Panicked(String::from("a < b")));
+        vec![
+            DayResult {
+                day: 1,
+                parts: Some(vec![
+                    result(1, 1, Some("42"), Some("42")),
+                    result(1, 2, Some("41"), Some("42")),
+                ]),
+            },
+            DayResult {
+                day: 2,
+                parts: Some(vec![panicked, result(2, 2, Some("1"), None)]),
+            },
+            DayResult {
+                day: 3,
+                parts: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_cases() {
+        let cases = cases(&days(), None);
+        let statuses = cases.iter().map(|c| &c.status).collect::<Vec<_>>();
+
+        assert_eq!(statuses.len(), 6);
+        assert_eq!(statuses[0], &Status::Passed);
+        assert_eq!(
+            statuses[1],
+            &Status::Failed {
+                kind: "wrong_answer",
+                message: String::from("expected 42, got 41")
+            }
+        );
+        assert!(matches!(statuses[2], Status::Failed { kind: "panic", .. }));
+        assert_eq!(statuses[3], &Status::Skipped("no stored answer".into()));
+        assert_eq!(statuses[4], &Status::Skipped("no input".into()));
+        assert_eq!(super::cases(&days(), Some(2)).len(), 5);
+    }
+
+    #[test]
+    fn test_to_junit() {
+        let junit = to_junit(&cases(&days(), None));
+
+        assert!(junit.contains(
+            r#"<testsuites name="advent_of_code" tests="6" failures="2" skipped="3" time="0.001000">"#
+        ));
+        assert!(junit.contains(
+            r#"<testsuite name="day01" tests="2" failures="1" skipped="0" time="0.000500">"#
+        ));
+        assert!(junit.contains(r#"<testcase classname="day01" name="part 1" time="0.000250"/>"#));
+        assert!(junit.contains(r#"<failure type="panic" message="panicked: a &lt; b"/>"#));
+        assert!(junit.contains(r#"<skipped message="no input"/>"#));
+        assert!(junit.ends_with("  </testsuite>\n</testsuites>\n"));
+    }
+
+    #[test]
+    fn test_to_tap() {
+        let tap = to_tap(&cases(&days(), None));
+
+        assert!(tap.starts_with(
+            "TAP version 13\n1..6\nok 1 - day 01 part 1\n  ---\n  duration_ms: 0.250\n  ...\n"
+        ));
+        assert!(tap.contains("not ok 2 - day 01 part 2\n  ---\n  type: wrong_answer\n  message: \"expected 42, got 41\"\n"));
+        assert!(tap.contains("ok 6 - day 03 part 2 # SKIP no input\n"));
+    }
+}
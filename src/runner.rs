@@ -0,0 +1,404 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::allocations::{self, AllocStats};
+use crate::answers;
+use crate::bench::{self, Stats};
+use crate::{render, Solution};
+
+/// Why a part did not produce an answer, besides not being solved yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartError {
+    /// The part ran longer than the given limit and was abandoned.
+    TimedOut(Duration),
+    /// The part panicked with the given message.
+    Panicked(String),
+}
+
+impl fmt::Display for PartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartError::TimedOut(limit) => {
+                write!(f, "timed out (limit: {})", render::duration(*limit))
+            }
+            PartError::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// Outcome of running one part of a solution against the puzzle input.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    /// Repeated timings, only present when benchmarking.
+    pub stats: Option<Stats>,
+    pub error: Option<PartError>,
+    /// Heap usage of the first run, only present with the `count-allocations` feature.
+    pub allocations: Option<AllocStats>,
+    /// The stored answer from `src/answers`, if known.
+    pub expected: Option<String>,
+}
+
+impl PartResult {
+    /// The timing used for totals and comparisons: the median when benchmarked,
+    /// the single run's elapsed time otherwise.
+    pub fn time(&self) -> Duration {
+        match &self.stats {
+            Some(stats) => stats.median,
+            None => self.elapsed,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    /// Whether the part produced an answer that differs from the stored one.
+    pub fn is_wrong(&self) -> bool {
+        match (&self.answer, &self.expected) {
+            (Some(answer), Some(expected)) => answer != expected,
+            _ => false,
+        }
+    }
+
+    pub fn is_failing(&self) -> bool {
+        self.error.is_some() || self.is_wrong()
+    }
+}
+
+/// Outcome of running a day against its puzzle input.
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u8,
+    /// `None` if the day has no input file.
+    pub parts: Option<Vec<PartResult>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    /// Measure solved parts repeatedly with `bench::measure`.
+    pub bench: bool,
+    /// Abandon parts that run longer than this.
+    pub timeout: Option<Duration>,
+    /// Only run this part, both parts if `None`.
+    pub part: Option<u8>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+/// Parts that timed out and are still running on their detached thread.
+/// They compete with everything that runs after them for the CPU.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of parts that timed out so far, see `Options::timeout`.
+/// Timings taken while any are left running are unreliable.
+pub fn abandoned_parts() -> usize {
+    ABANDONED.load(Ordering::Relaxed)
+}
+
+/// Calls `func` on a separate thread, so panics are caught and a call that
+/// exceeds `timeout` can be left running in the background while we move on.
+pub(crate) fn run_isolated<T: Send + 'static>(
+    func: impl FnOnce() -> T + Send + 'static,
+    timeout: Option<Duration>,
+) -> Result<T, PartError> {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        // the receiver is gone if we already timed out.
+        let _ = sender.send(func());
+    });
+
+    let received = match timeout {
+        Some(limit) => receiver.recv_timeout(limit).map_err(|e| match e {
+            RecvTimeoutError::Timeout => Some(limit),
+            RecvTimeoutError::Disconnected => None,
+        }),
+        None => receiver.recv().map_err(|_| None),
+    };
+
+    received.map_err(|limit| match limit {
+        Some(limit) => {
+            ABANDONED.fetch_add(1, Ordering::Relaxed);
+            PartError::TimedOut(limit)
+        }
+        None => PartError::Panicked(match handle.join() {
+            Err(payload) => panic_message(payload.as_ref()),
+            Ok(_) => String::from("no answer"),
+        }),
+    })
+}
+
+/// Runs a part once with `run_isolated`, timing it and tracking its allocations.
+fn run_part(
+    func: fn(&str) -> Option<String>,
+    input: &Arc<str>,
+    timeout: Option<Duration>,
+) -> (
+    Result<Option<String>, PartError>,
+    Duration,
+    Option<AllocStats>,
+) {
+    let input = Arc::clone(input);
+    let received = run_isolated(
+        move || {
+            allocations::track(|| {
+                let timer = Instant::now();
+                let answer = func(&input);
+                (answer, timer.elapsed())
+            })
+        },
+        timeout,
+    );
+
+    match received {
+        Ok(((answer, elapsed), allocations)) => (Ok(answer), elapsed, allocations),
+        Err(PartError::TimedOut(limit)) => (Err(PartError::TimedOut(limit)), limit, None),
+        Err(error) => (Err(error), Duration::ZERO, None),
+    }
+}
+
+/// Runs both parts of `solution` once, see `Options` for benchmarking and time limits.
+pub fn run_solution(solution: &Solution, input: &str, options: Options) -> Vec<PartResult> {
+    let shared_input: Arc<str> = Arc::from(input);
+    solution
+        .parts
+        .iter()
+        .zip(1..)
+        .filter(|(_, part)| options.part.is_none_or(|selected| selected == *part))
+        .map(|(&func, part)| {
+            let (answer, elapsed, allocations) = run_part(func, &shared_input, options.timeout);
+            let (answer, error) = match answer {
+                Ok(answer) => (answer, None),
+                Err(error) => (None, Some(error)),
+            };
+
+            // measuring repeats the part, so it gets a limit that scales with the timeout.
+            let (stats, error) = match answer {
+                Some(_) if options.bench => {
+                    let input = Arc::clone(&shared_input);
+                    match run_isolated(
+                        move || bench::measure(func, &input),
+                        options.timeout.map(bench::time_limit),
+                    ) {
+                        Ok(stats) => (Some(stats), None),
+                        Err(error) => (None, Some(error)),
+                    }
+                }
+                _ => (None, error),
+            };
+
+            PartResult {
+                day: solution.day,
+                part,
+                answer,
+                elapsed,
+                stats,
+                error,
+                allocations,
+                expected: None,
+            }
+        })
+        .collect()
+}
+
+/// Reads the day's input and runs it with `run_solution`, checking the
+/// answers against the ones stored in `src/answers`.
+pub fn run_day(solution: &Solution, options: Options) -> DayResult {
+    let parts = crate::try_read_file("inputs", solution.day).map(|input| {
+        let expected = answers::load(solution.day);
+        let mut parts = run_solution(solution, &input, options);
+        for part in &mut parts {
+            part.expected = expected[part.part as usize - 1].clone();
+        }
+        parts
+    });
+
+    DayResult {
+        day: solution.day,
+        parts,
+    }
+}
+
+/// Runs every solution on up to `jobs` threads.
+///
+/// `on_result` is called in the order of `solutions`, as soon as a day and all
+/// days before it have finished. With a single job, days run one after another
+/// on the calling thread, which keeps timings free of interference.
+pub fn run_days(
+    solutions: &[Solution],
+    jobs: usize,
+    options: Options,
+    mut on_result: impl FnMut(&DayResult),
+) -> Vec<DayResult> {
+    if jobs <= 1 {
+        return solutions
+            .iter()
+            .map(|solution| {
+                let result = run_day(solution, options);
+                on_result(&result);
+                result
+            })
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(solutions.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                match solutions.get(idx) {
+                    Some(solution) => sender.send((idx, run_day(solution, options))).unwrap(),
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut results = Vec::with_capacity(solutions.len());
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&results.len()) {
+                on_result(&result);
+                results.push(result);
+            }
+        }
+        results
+    })
+}
+
+/// Prints a day's header followed by its parts.
+pub fn print_day(result: &DayResult) {
+    render::print_day_header(result.day);
+
+    match &result.parts {
+        Some(parts) => parts.iter().for_each(print_part),
+        None => println!("No input."),
+    }
+}
+
+/// Prints a part in the same format as `solve!`.
+pub fn print_part(result: &PartResult) {
+    let mode = render::mode();
+    let mut line = match (&result.answer, &result.stats) {
+        (Some(answer), Some(stats)) => format!("{} {}", answer, stats),
+        (Some(answer), None) => mode.answer(answer, result.elapsed, None),
+        (None, _) => match &result.error {
+            Some(error) => format!("{}.", error),
+            None => String::from("not solved."),
+        },
+    };
+    if result.is_wrong() {
+        let expected = result.expected.as_deref().unwrap_or_default();
+        line.push_str(&format!(
+            " {}",
+            mode.bold(format!("(expected: {})", expected))
+        ));
+    }
+    if let Some(allocations) = &result.allocations {
+        line.push_str(&format!(" {}", mode.italic(format!("({})", allocations))));
+    }
+    render::print_part(result.part, &line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(day: u8) -> Solution {
+        Solution {
+            day,
+            parts: [|input| Some(input.len().to_string()), |_| None],
+            generate: None,
+        }
+    }
+
+    #[test]
+    fn test_run_solution() {
+        let parts = run_solution(&solution(3), "abc", Options::default());
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!((parts[0].day, parts[0].part), (3, 1));
+        assert_eq!(parts[0].answer.as_deref(), Some("3"));
+        assert!(!parts[1].is_solved());
+
+        let mut part = parts[0].clone();
+        part.expected = Some(String::from("4"));
+        assert!(part.is_wrong() && part.is_failing());
+        part.expected = Some(String::from("3"));
+        assert!(!part.is_wrong() && !part.is_failing());
+
+        let options = Options {
+            part: Some(2),
+            ..Options::default()
+        };
+        let parts = run_solution(&solution(3), "abc", options);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].part, 2);
+    }
+
+    #[test]
+    fn test_run_solution_failures() {
+        let solution = Solution {
+            day: 1,
+            parts: [
+                |_| loop {
+                    std::thread::park();
+                },
+                |_| panic!("oh no"),
+            ],
+            generate: None,
+        };
+        let options = Options {
+            timeout: Some(Duration::from_millis(50)),
+            ..Options::default()
+        };
+        let parts = run_solution(&solution, "", options);
+
+        assert_eq!(
+            parts[0].error,
+            Some(PartError::TimedOut(Duration::from_millis(50)))
+        );
+        assert_eq!(parts[1].error, Some(PartError::Panicked("oh no".into())));
+        assert!(abandoned_parts() >= 1);
+    }
+
+    #[test]
+    fn test_run_days_in_order() {
+        let solutions = (1..=25).map(solution).collect::<Vec<_>>();
+
+        let mut reported = vec![];
+        let results = run_days(&solutions, 4, Options::default(), |result| {
+            reported.push(result.day)
+        });
+
+        let expected = (1..=25).collect::<Vec<_>>();
+        assert_eq!(reported, expected);
+        assert_eq!(results.iter().map(|r| r.day).collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,362 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::{self, Write as _};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::runner::run_isolated;
+use crate::{bench, render};
+
+/// Complexity classes timings are fitted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Class {
+    pub const ALL: [Class; 6] = [
+        Class::Constant,
+        Class::Logarithmic,
+        Class::Linear,
+        Class::Linearithmic,
+        Class::Quadratic,
+        Class::Cubic,
+    ];
+
+    /// `ln f(n)` of the class' growth function `f`.
+    fn ln_growth(self, n: f64) -> f64 {
+        let n = n.max(2_f64);
+        match self {
+            Class::Constant => 0_f64,
+            Class::Logarithmic => n.ln().ln(),
+            Class::Linear => n.ln(),
+            Class::Linearithmic => n.ln() + n.ln().ln(),
+            Class::Quadratic => 2_f64 * n.ln(),
+            Class::Cubic => 3_f64 * n.ln(),
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Class::Constant => "O(1)",
+            Class::Logarithmic => "O(log n)",
+            Class::Linear => "O(n)",
+            Class::Linearithmic => "O(n log n)",
+            Class::Quadratic => "O(n^2)",
+            Class::Cubic => "O(n^3)",
+        };
+        f.pad(name)
+    }
+}
+
+/// The median time of a part on a generated input of `size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub size: usize,
+    pub time: Duration,
+}
+
+/// How well a class describes the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub class: Class,
+    /// Root mean square of the residuals of `ln t = ln c + ln f(n)`,
+    /// 0.1 is roughly a 10% deviation per sample.
+    pub error: f64,
+}
+
+/// Result of a scaling run of a single part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub day: u8,
+    pub part: u8,
+    pub samples: Vec<Sample>,
+    /// Fits of every class, best first.
+    pub fits: Vec<Fit>,
+    /// Slope of the least squares line through the log-log samples, the
+    /// empirical exponent `k` in `t ~ n^k`.
+    pub slope: f64,
+    /// Why the series ended before the largest size, e.g. `timed out (limit: 1.00s) at n = 64000`.
+    pub stopped: Option<String>,
+}
+
+/// Parses the number of sizes to analyze, a fit needs at least two.
+pub fn parse_steps(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(steps @ 2..) => Ok(steps),
+        _ => Err(format!(
+            "invalid number of steps \"{}\", expected 2 or more",
+            value
+        )),
+    }
+}
+
+/// `count` sizes starting at `start`, each `factor` times the previous.
+pub fn sizes(start: usize, factor: usize, count: usize) -> Vec<usize> {
+    std::iter::successors(Some(start.max(1)), |size| size.checked_mul(factor))
+        .take(count)
+        .collect()
+}
+
+fn ln_time(sample: &Sample) -> f64 {
+    (sample.time.as_nanos().max(1) as f64).ln()
+}
+
+/// Fits the samples against every class, the constant factor of each is
+/// chosen to minimize the error in log space.
+pub fn fit(samples: &[Sample]) -> Vec<Fit> {
+    let mut fits = Class::ALL
+        .iter()
+        .map(|&class| {
+            let residuals = samples
+                .iter()
+                .map(|sample| ln_time(sample) - class.ln_growth(sample.size as f64))
+                .collect::<Vec<_>>();
+            let ln_factor = residuals.iter().sum::<f64>() / residuals.len() as f64;
+            let variance = residuals
+                .iter()
+                .map(|residual| (residual - ln_factor).powi(2))
+                .sum::<f64>()
+                / residuals.len() as f64;
+            Fit {
+                class,
+                error: variance.sqrt(),
+            }
+        })
+        .collect::<Vec<_>>();
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// Slope of the least squares line through `(ln n, ln t)`.
+pub fn slope(samples: &[Sample]) -> f64 {
+    let points = samples
+        .iter()
+        .map(|sample| ((sample.size as f64).ln(), ln_time(sample)))
+        .collect::<Vec<_>>();
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let (covariance, variance) = points.iter().fold((0_f64, 0_f64), |(cov, var), (x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x).powi(2),
+        )
+    });
+    if variance == 0_f64 {
+        0_f64
+    } else {
+        covariance / variance
+    }
+}
+
+/// Times `func` on the input of `size`, isolated like the runner does so that
+/// panics are caught and `timeout` is applied to the run and its measurement.
+fn sample(
+    func: fn(&str) -> Option<String>,
+    generate: fn(usize) -> String,
+    size: usize,
+    timeout: Option<Duration>,
+) -> Result<Sample, String> {
+    let input: Arc<str> = run_isolated(move || Arc::from(generate(size)), None)
+        .map_err(|e| format!("generating the input {}", e))?;
+    let answer = {
+        let input = Arc::clone(&input);
+        run_isolated(move || func(&input), timeout).map_err(|e| e.to_string())?
+    };
+    if answer.is_none() {
+        return Err(String::from("not solved"));
+    }
+    let stats = run_isolated(
+        move || bench::measure(func, &input),
+        timeout.map(bench::time_limit),
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Sample {
+        size,
+        time: stats.median,
+    })
+}
+
+/// Benchmarks `func` on generated inputs of every size in `sizes`. The series
+/// stops at the first size that is not solved, panics or exceeds `timeout`,
+/// and only the sizes before it are fitted. Fails if fewer than two are left.
+pub fn analyze(
+    day: u8,
+    part: u8,
+    func: fn(&str) -> Option<String>,
+    generate: fn(usize) -> String,
+    sizes: &[usize],
+    timeout: Option<Duration>,
+) -> Result<Analysis, String> {
+    let mut samples = vec![];
+    let mut stopped = None;
+    for &size in sizes {
+        match sample(func, generate, size, timeout) {
+            Ok(sample) => samples.push(sample),
+            Err(reason) => {
+                stopped = Some(format!("{} at n = {}", reason, size));
+                break;
+            }
+        }
+    }
+
+    if samples.len() < 2 {
+        return Err(stopped.unwrap_or_else(|| String::from("fewer than two sizes")));
+    }
+    Ok(Analysis {
+        day,
+        part,
+        fits: fit(&samples),
+        slope: slope(&samples),
+        samples,
+        stopped,
+    })
+}
+
+impl Analysis {
+    pub fn best(&self) -> Class {
+        self.fits[0].class
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {:02} Part {}: {} {}",
+            self.day,
+            self.part,
+            render::bold(self.best()),
+            render::italic(format!("(slope: {:.2})", self.slope))
+        )?;
+
+        let mut table = format!(
+            "{:>10}  {:>12}  {:>7}  {:>8}  {:>5}\n",
+            "n", "time", "log10 n", "log10 ns", "slope"
+        );
+        for (idx, sample) in self.samples.iter().enumerate() {
+            let log_n = (sample.size as f64).log10();
+            let log_t = (sample.time.as_nanos().max(1) as f64).log10();
+            let local_slope = match idx.checked_sub(1).map(|prev| &self.samples[prev]) {
+                Some(prev) => format!(
+                    "{:.2}",
+                    (log_t - (prev.time.as_nanos().max(1) as f64).log10())
+                        / (log_n - (prev.size as f64).log10())
+                ),
+                None => String::from("-"),
+            };
+            writeln!(
+                table,
+                "{:>10}  {:>12}  {:>7.2}  {:>8.2}  {:>5}",
+                sample.size,
+                render::duration(sample.time),
+                log_n,
+                log_t,
+                local_slope
+            )?;
+        }
+        write!(f, "{}", table)?;
+
+        if let Some(stopped) = &self.stopped {
+            writeln!(f, "{}", render::italic(format!("stopped: {}", stopped)))?;
+        }
+
+        writeln!(f, "{:<12}  {:>6}", "class", "error")?;
+        for fit in &self.fits {
+            writeln!(f, "{:<12}  {:>6.3}", fit.class, fit.error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(time: impl Fn(f64) -> f64) -> Vec<Sample> {
+        sizes(1000, 2, 8)
+            .into_iter()
+            .map(|size| Sample {
+                size,
+                time: Duration::from_nanos(time(size as f64) as u64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(sizes(1000, 2, 4), vec![1000, 2000, 4000, 8000]);
+        assert_eq!(sizes(usize::MAX, 2, 3), vec![usize::MAX]);
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(parse_steps("8"), Ok(8));
+        assert!(parse_steps("1").is_err());
+        assert!(parse_steps("0").is_err());
+        assert!(parse_steps("many").is_err());
+    }
+
+    #[test]
+    fn test_fit() {
+        let cases = [
+            (Class::Constant, (|_| 5_000_f64) as fn(f64) -> f64),
+            (Class::Linear, |n| 3_f64 * n),
+            (Class::Linearithmic, |n| 3_f64 * n * n.ln()),
+            (Class::Quadratic, |n| n * n / 10_f64),
+            (Class::Cubic, |n| n * n * n / 1000_f64),
+        ];
+        for (class, time) in cases {
+            let samples = samples(time);
+            assert_eq!(fit(&samples)[0].class, class);
+        }
+
+        assert!((slope(&samples(|n| n * n)) - 2_f64).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_analyze_stops_at_failures() {
+        let generate = |size: usize| "x".repeat(size);
+        let failing = |input: &str| match input.len() {
+            1 => Some(String::from("1")),
+            2 => None,
+            _ => panic!("too large"),
+        };
+        let error = analyze(1, 1, failing, generate, &[1, 2, 4], None).unwrap_err();
+        assert_eq!(error, "not solved at n = 2");
+
+        let panicking = |input: &str| match input.len() {
+            1 | 2 => Some(String::from("1")),
+            _ => panic!("too large"),
+        };
+        let analysis = analyze(1, 1, panicking, generate, &[1, 2, 4, 8], None).unwrap();
+        assert_eq!(analysis.samples.len(), 2);
+        assert_eq!(
+            analysis.stopped.as_deref(),
+            Some("panicked: too large at n = 4")
+        );
+
+        let error = analyze(
+            1,
+            1,
+            |_| loop {
+                std::thread::park();
+            },
+            generate,
+            &[1, 2],
+            Some(Duration::from_millis(20)),
+        )
+        .unwrap_err();
+        assert!(error.starts_with("timed out"));
+    }
+}
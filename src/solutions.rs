@@ -0,0 +1,35 @@
+/*
+ * This file is maintained by `cargo scaffold`.
+ * It registers every day so `cargo all` and `cargo bench` can call solutions in-process.
+ */
+#![allow(dead_code, unused_imports)]
+
+use advent_of_code::{solution, Solution};
+
+#[path = "bin/01.rs"]
+mod day01;
+#[path = "bin/02.rs"]
+mod day02;
+#[path = "bin/03.rs"]
+mod day03;
+#[path = "bin/04.rs"]
+mod day04;
+#[path = "bin/05.rs"]
+mod day05;
+#[path = "bin/06.rs"]
+mod day06;
+#[path = "bin/07.rs"]
+mod day07;
+#[path = "bin/08.rs"]
+mod day08;
+
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, day01),
+    solution!(2, day02),
+    solution!(3, day03, generate),
+    solution!(4, day04),
+    solution!(5, day05),
+    solution!(6, day06, generate),
+    solution!(7, day07),
+    solution!(8, day08),
+];
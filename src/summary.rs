@@ -0,0 +1,180 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::render::Mode;
+use crate::runner::PartResult;
+
+const MAX_ANSWER_WIDTH: usize = 20;
+
+/// Row order of the summary table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    Time,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortOrder::Day),
+            "time" => Ok(SortOrder::Time),
+            _ => Err(format!(
+                "invalid sort order \"{}\", expected day or time",
+                s
+            )),
+        }
+    }
+}
+
+/// Sum of the timings of all solved parts.
+pub fn total_time(results: &[PartResult]) -> Duration {
+    results
+        .iter()
+        .filter(|result| result.is_solved())
+        .map(PartResult::time)
+        .sum()
+}
+
+fn truncate(answer: &str, width: usize, mode: Mode) -> String {
+    if answer.chars().count() <= width {
+        answer.to_string()
+    } else {
+        let truncated = answer.chars().take(width - 1).collect::<String>();
+        format!("{}{}", truncated, mode.symbol("…", "~"))
+    }
+}
+
+/// Renders a table of all parts with their answer, time and share of the total time.
+/// The slowest solved part is highlighted.
+pub fn render_summary(results: &[PartResult], order: SortOrder, mode: Mode) -> String {
+    let total = total_time(results);
+    let slowest = results
+        .iter()
+        .filter(|result| result.is_solved())
+        .max_by_key(|result| result.time())
+        .map(|result| (result.day, result.part));
+
+    let mut rows = results.iter().collect::<Vec<_>>();
+    if order == SortOrder::Time {
+        rows.sort_by_key(|result| (!result.is_solved(), std::cmp::Reverse(result.time())));
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<4} {:<5} {:<width$} {:>10} {:>7}",
+        "Day",
+        "Part",
+        "Answer",
+        "Time",
+        "Share",
+        width = MAX_ANSWER_WIDTH
+    )
+    .unwrap();
+
+    for result in rows {
+        let answer = match (&result.answer, &result.error) {
+            (Some(answer), _) => truncate(answer, MAX_ANSWER_WIDTH, mode),
+            (None, Some(error)) => truncate(&error.to_string(), MAX_ANSWER_WIDTH, mode),
+            (None, None) => String::from("not solved"),
+        };
+        let (time, share) = if result.is_solved() {
+            let share = if total.is_zero() {
+                0_f64
+            } else {
+                result.time().as_secs_f64() / total.as_secs_f64() * 100_f64
+            };
+            (mode.duration(result.time()), format!("{:.1}%", share))
+        } else {
+            (String::from("-"), String::from("-"))
+        };
+
+        let row = format!(
+            "{:<4} {:<5} {:<width$} {:>10} {:>7}",
+            format!("{:02}", result.day),
+            result.part,
+            answer,
+            time,
+            share,
+            width = MAX_ANSWER_WIDTH
+        );
+        if slowest == Some((result.day, result.part)) {
+            writeln!(out, "{}", mode.bold(format!("{} <- slowest", row))).unwrap();
+        } else {
+            writeln!(out, "{}", row).unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartError;
+
+    fn result(day: u8, part: u8, micros: u64, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: answer.map(String::from),
+            elapsed: Duration::from_micros(micros),
+            stats: None,
+            error: None,
+            allocations: None,
+            expected: None,
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10, Mode::Color), "short");
+        assert_eq!(
+            truncate("a very long answer", 10, Mode::Color),
+            "a very lo…"
+        );
+        assert_eq!(
+            truncate("a very long answer", 10, Mode::Plain),
+            "a very lo~"
+        );
+    }
+
+    #[test]
+    fn test_render_summary() {
+        let mut timed_out = result(2, 2, 0, None);
+        timed_out.error = Some(PartError::TimedOut(Duration::from_secs(1)));
+        let results = [
+            result(1, 1, 250, Some("42")),
+            result(1, 2, 750, Some("CMZ")),
+            result(2, 1, 0, None),
+            timed_out,
+        ];
+
+        let summary = render_summary(&results, SortOrder::Day, Mode::Color);
+        let lines = summary.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("01   1     42"));
+        assert!(lines[1].ends_with("250.00µs   25.0%"));
+        assert!(lines[2].contains("75.0% <- slowest"));
+        assert!(lines[3].contains("not solved"));
+        assert!(lines[4].contains("timed out (limit: 1…"));
+
+        assert!(lines[2].starts_with(crate::ANSI_BOLD));
+
+        let by_time = render_summary(&results, SortOrder::Time, Mode::Plain);
+        assert!(by_time.lines().nth(1).unwrap().contains("CMZ"));
+        assert!(by_time
+            .lines()
+            .nth(2)
+            .unwrap()
+            .ends_with("250.00us   25.0%"));
+        assert_eq!(total_time(&results), Duration::from_micros(1000));
+    }
+}
@@ -0,0 +1,174 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt;
+use std::fs;
+
+/// Settings in effect while benchmarking, recorded with the results.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tuning {
+    /// The core the runner is pinned to.
+    pub core: Option<usize>,
+    /// The nice value after raising the priority, lower runs first.
+    pub nice: Option<i32>,
+    /// The frequency scaling governor of the core timings are taken on.
+    pub governor: Option<String>,
+}
+
+impl Tuning {
+    /// Whether frequency scaling is known to be set up for anything but
+    /// steady performance.
+    pub fn governor_warning(&self) -> Option<String> {
+        match self.governor.as_deref() {
+            Some("performance") | None => None,
+            Some(governor) => Some(format!(
+                "CPU governor is \"{}\", timings may vary with frequency scaling. Use \"performance\" for stable numbers.",
+                governor
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.core {
+            Some(core) => write!(f, "pinned to core {}", core)?,
+            None => write!(f, "not pinned")?,
+        }
+        match self.nice {
+            Some(nice) => write!(f, ", nice {}", nice)?,
+            None => write!(f, ", default priority")?,
+        }
+        write!(
+            f,
+            ", governor: {}",
+            self.governor.as_deref().unwrap_or("unknown")
+        )
+    }
+}
+
+/// Reads the scaling governor of `core` from sysfs.
+pub fn governor(core: usize) -> Option<String> {
+    let path = format!(
+        "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor",
+        core
+    );
+    fs::read_to_string(path)
+        .ok()
+        .map(|governor| governor.trim().to_string())
+        .filter(|governor| !governor.is_empty())
+}
+
+/// Restricts the calling thread, and threads it spawns afterwards, to `core`.
+#[cfg(target_os = "linux")]
+pub fn pin_to_core(core: usize) -> Result<(), String> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(format!("core {} does not exist", core));
+    }
+    // SAFETY: `set` is a plain bitmask that outlives the call, and `core` is in bounds.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    match result {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error().to_string()),
+    }
+}
+
+/// Lowers the nice value of the calling thread as far as `RLIMIT_NICE`
+/// permits, threads spawned afterwards inherit it. Returns the new value.
+#[cfg(target_os = "linux")]
+pub fn raise_priority() -> Result<i32, String> {
+    // SAFETY: both calls only read or write the calling thread's scheduling
+    // state and the `limit` struct owned by this function.
+    unsafe {
+        let current = libc::getpriority(libc::PRIO_PROCESS, 0);
+        let mut limit: libc::rlimit = std::mem::zeroed();
+        // an `RLIMIT_NICE` of `n` allows nice values down to `20 - n`.
+        let lowest = match libc::getrlimit(libc::RLIMIT_NICE, &mut limit) {
+            0 if limit.rlim_cur == libc::RLIM_INFINITY => -20,
+            0 => (20 - limit.rlim_cur.min(40) as i32).max(-20),
+            _ => current,
+        };
+        // root may exceed the limit.
+        let target = if libc::geteuid() == 0 { -20 } else { lowest };
+        if target >= current {
+            return Err(format!(
+                "not permitted, nice is {} (see RLIMIT_NICE)",
+                current
+            ));
+        }
+        match libc::setpriority(libc::PRIO_PROCESS, 0, target) {
+            0 => Ok(target),
+            _ => Err(std::io::Error::last_os_error().to_string()),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_core(_core: usize) -> Result<(), String> {
+    Err(String::from("only supported on Linux"))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn raise_priority() -> Result<i32, String> {
+    Err(String::from("only supported on Linux"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Tuning::default().to_string(),
+            "not pinned, default priority, governor: unknown"
+        );
+
+        let tuning = Tuning {
+            core: Some(2),
+            nice: Some(-20),
+            governor: Some(String::from("powersave")),
+        };
+        assert_eq!(
+            tuning.to_string(),
+            "pinned to core 2, nice -20, governor: powersave"
+        );
+        assert!(tuning.governor_warning().is_some());
+    }
+
+    /// The first core the calling thread may run on, core 0 may be excluded
+    /// e.g. by `taskset` or a container's cpuset.
+    #[cfg(target_os = "linux")]
+    fn first_allowed_core() -> usize {
+        // SAFETY: `set` is a plain bitmask that outlives the call.
+        let set = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            assert_eq!(
+                libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set),
+                0
+            );
+            set
+        };
+        // SAFETY: every `core` is below `CPU_SETSIZE`.
+        (0..libc::CPU_SETSIZE as usize)
+            .find(|&core| unsafe { libc::CPU_ISSET(core, &set) })
+            .unwrap()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_pin_to_core() {
+        // on a separate thread to leave the test harness alone.
+        std::thread::spawn(|| {
+            assert_eq!(pin_to_core(first_allowed_core()), Ok(()));
+            assert!(pin_to_core(100_000).is_err());
+        })
+        .join()
+        .unwrap();
+    }
+}